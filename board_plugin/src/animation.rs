@@ -0,0 +1,399 @@
+use crate::components::{BoardId, Bomb, Coordinates};
+use crate::events::BombExplosionEvent;
+use crate::resources::Board;
+use crate::systems;
+use crate::PauseState;
+use bevy::log;
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+use std::f32::consts::TAU;
+
+/// Downward acceleration of explosion particles, in tiles per second squared
+const PARTICLE_GRAVITY: f32 = 30.;
+
+/// Animation settings
+///
+/// Animations are purely visual, the board state is always updated at once.
+#[derive(Debug, Clone, Resource)]
+pub struct AnimationOptions {
+    /// Covers and flags are updated instantly when disabled
+    pub enabled: bool,
+    /// Duration of the cover fade out, in seconds
+    pub cover_duration: f32,
+    /// Delay of the reveal wave per tile of distance from the click
+    pub wave_delay: f32,
+    /// Duration of the flag drop in
+    pub flag_duration: f32,
+    /// Duration of the board shake on explosions
+    pub shake_duration: f32,
+    /// Maximum board offset of the shake, in tiles
+    pub shake_strength: f32,
+    /// Number of particles spawned by an explosion
+    pub particle_count: usize,
+    /// Lifetime of explosion particles
+    pub particle_lifetime: f32,
+    /// Key finishing every running animation
+    pub skip_key: Option<KeyCode>,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cover_duration: 0.2,
+            wave_delay: 0.03,
+            flag_duration: 0.2,
+            shake_duration: 0.4,
+            shake_strength: 0.25,
+            particle_count: 24,
+            particle_lifetime: 0.8,
+            skip_key: Some(KeyCode::Tab),
+        }
+    }
+}
+
+/// Cover fading out after its tile was uncovered
+#[derive(Debug, Component)]
+pub(crate) struct CoverFade {
+    delay: Timer,
+    timer: Timer,
+}
+
+impl CoverFade {
+    /// Fade starting after a delay growing with `distance` from the click
+    pub(crate) fn new(options: &AnimationOptions, distance: f32) -> Self {
+        Self {
+            delay: Timer::from_seconds(
+                options.wave_delay * distance,
+                TimerMode::Once,
+            ),
+            timer: Timer::from_seconds(options.cover_duration, TimerMode::Once),
+        }
+    }
+}
+
+/// Flag dropping onto its tile
+#[derive(Debug, Component)]
+pub(crate) struct FlagDrop {
+    timer: Timer,
+    /// Starting height above the tile
+    height: f32,
+}
+
+impl FlagDrop {
+    /// Scale of the flag when it starts falling
+    pub(crate) const START_SCALE: f32 = 1.5;
+
+    pub(crate) fn new(options: &AnimationOptions, height: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(options.flag_duration, TimerMode::Once),
+            height,
+        }
+    }
+
+    /// Transform of the flag at the start of the drop
+    #[must_use]
+    pub(crate) fn start_transform(&self, z: f32) -> Transform {
+        Transform::from_xyz(0., self.height, z)
+            .with_scale(Vec3::splat(Self::START_SCALE))
+    }
+}
+
+/// Board shaking after an explosion
+#[derive(Debug, Component)]
+struct Shake {
+    timer: Timer,
+    /// Board translation to come back to
+    origin: Vec3,
+    /// Maximum offset, in pixels
+    strength: f32,
+}
+
+/// Explosion debris
+#[derive(Debug, Component)]
+struct Particle {
+    timer: Timer,
+    velocity: Vec3,
+}
+
+/// Bomb tile whose explosion was already played
+#[derive(Debug, Component)]
+struct Exploded;
+
+/// Tile animations: cover fade and reveal wave, flag drop in, explosion shake
+/// and particles
+pub struct AnimationPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for AnimationPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnimationOptions>().add_systems(
+            Update,
+            (
+                Self::skip_animations,
+                (
+                    Self::fade_covers,
+                    Self::drop_flags,
+                    Self::explode.after(systems::uncover::uncover_tiles),
+                    Self::shake_boards,
+                    Self::move_particles,
+                ),
+            )
+                .chain()
+                .run_if(in_state(self.running_state.clone()))
+                .run_if(in_state(PauseState::Running)),
+        );
+
+        log::info!("Loaded Animation Plugin");
+    }
+}
+
+/// Fading covers, drawn as plain or atlas sprites
+type FadingCovers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut CoverFade,
+        &'static mut Transform,
+        Option<&'static mut Sprite>,
+        Option<&'static mut TextureAtlasSprite>,
+    ),
+>;
+
+/// Bomb tiles that didn't blow up yet
+type UnexplodedBombs<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Parent,
+        &'static Coordinates,
+        &'static Transform,
+    ),
+    (With<Bomb>, Without<Exploded>),
+>;
+
+impl<T> AnimationPlugin<T> {
+    /// Bring every running animation to its end, on the skip key or when
+    /// animations get disabled
+    fn skip_animations(
+        options: Res<AnimationOptions>,
+        keys: Res<Input<KeyCode>>,
+        mut covers: Query<&mut CoverFade>,
+        mut flags: Query<&mut FlagDrop>,
+        mut shakes: Query<&mut Shake>,
+        mut particles: Query<&mut Particle>,
+    ) {
+        let skip = options.skip_key.is_some_and(|key| keys.just_pressed(key));
+        if options.enabled && !skip {
+            return;
+        }
+
+        let finish = |timer: &mut Timer| timer.set_elapsed(timer.duration());
+        for mut cover in covers.iter_mut() {
+            finish(&mut cover.delay);
+            finish(&mut cover.timer);
+        }
+        for mut flag in flags.iter_mut() {
+            finish(&mut flag.timer);
+        }
+        for mut shake in shakes.iter_mut() {
+            finish(&mut shake.timer);
+        }
+        for mut particle in particles.iter_mut() {
+            finish(&mut particle.timer);
+        }
+    }
+
+    /// Fade and shrink uncovered covers, once the reveal wave reaches them
+    fn fade_covers(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut covers: FadingCovers,
+    ) {
+        for (entity, mut fade, mut transform, sprite, atlas_sprite) in
+            covers.iter_mut()
+        {
+            if !fade.delay.tick(time.delta()).finished() {
+                continue;
+            }
+            if fade.timer.tick(time.delta()).finished() {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+
+            let t = fade.timer.percent();
+            if let Some(mut sprite) = sprite {
+                sprite.color.set_a(1. - t);
+            }
+            if let Some(mut sprite) = atlas_sprite {
+                sprite.color.set_a(1. - t);
+            }
+            transform.scale = Vec3::splat(1. - t / 2.);
+        }
+    }
+
+    /// Drop new flags onto their tile
+    fn drop_flags(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut flags: Query<(Entity, &mut FlagDrop, &mut Transform)>,
+    ) {
+        for (entity, mut drop, mut transform) in flags.iter_mut() {
+            // Ease in, the flag accelerates as it falls
+            let t = drop.timer.tick(time.delta()).percent();
+            let remaining = 1. - t * t;
+            transform.translation.y = drop.height * remaining;
+            transform.scale =
+                Vec3::splat(1. + (FlagDrop::START_SCALE - 1.) * remaining);
+
+            if drop.timer.finished() {
+                commands.entity(entity).remove::<FlagDrop>();
+            }
+        }
+    }
+
+    /// Shake the board and blow particles out of its uncovered bombs
+    fn explode(
+        mut commands: Commands,
+        options: Res<AnimationOptions>,
+        mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+        boards: Query<(Entity, &BoardId, &Board, &Transform, Option<&Shake>)>,
+        bombs: UnexplodedBombs,
+    ) {
+        if !options.enabled {
+            bomb_explosion_event_reader.clear();
+            return;
+        }
+
+        for event in bomb_explosion_event_reader.iter() {
+            let Some((board_entity, _, board, transform, shake)) =
+                boards.iter().find(|(_, id, ..)| **id == event.board)
+            else {
+                continue;
+            };
+
+            commands.entity(board_entity).insert(Shake {
+                timer: Timer::from_seconds(
+                    options.shake_duration,
+                    TimerMode::Once,
+                ),
+                origin: shake.map_or(transform.translation, |s| s.origin),
+                strength: options.shake_strength * board.tile_size,
+            });
+
+            let exploded = bombs.iter().filter(|(_, parent, coords, _)| {
+                parent.get() == board_entity
+                    && !board.covered_tiles.contains_key(*coords)
+            });
+            for (entity, _, _, bomb_transform) in exploded {
+                commands.entity(entity).insert(Exploded);
+                Self::spawn_particles(
+                    &mut commands,
+                    &options,
+                    board_entity,
+                    bomb_transform.translation,
+                    board.tile_size,
+                );
+            }
+        }
+    }
+
+    /// Spawn explosion particles as children of the board
+    fn spawn_particles(
+        commands: &mut Commands,
+        options: &AnimationOptions,
+        board_entity: Entity,
+        position: Vec3,
+        tile_size: f32,
+    ) {
+        let mut rng = thread_rng();
+
+        commands.entity(board_entity).with_children(|parent| {
+            for _ in 0..options.particle_count {
+                let angle = rng.gen_range(0. ..TAU);
+                let speed = rng.gen_range(2. ..8.) * tile_size;
+                // Anywhere between red and orange
+                let color = Color::rgb(1., rng.gen_range(0.1..0.6), 0.);
+
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(tile_size / 6.)),
+                            color,
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(
+                            position.x, position.y, 10.,
+                        ),
+                        ..default()
+                    })
+                    .insert(Name::new("Explosion Particle"))
+                    .insert(Particle {
+                        timer: Timer::from_seconds(
+                            options.particle_lifetime,
+                            TimerMode::Once,
+                        ),
+                        velocity: Vec3::new(angle.cos(), angle.sin(), 0.)
+                            * speed,
+                    });
+            }
+        });
+    }
+
+    /// Offset shaking boards randomly, settling back as the shake ends
+    fn shake_boards(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut boards: Query<(Entity, &mut Shake, &mut Transform)>,
+    ) {
+        let mut rng = thread_rng();
+
+        for (entity, mut shake, mut transform) in boards.iter_mut() {
+            if shake.timer.tick(time.delta()).finished() {
+                transform.translation = shake.origin;
+                commands.entity(entity).remove::<Shake>();
+                continue;
+            }
+
+            let strength = shake.strength * shake.timer.percent_left();
+            let offset =
+                Vec2::new(rng.gen_range(-1. ..=1.), rng.gen_range(-1. ..=1.))
+                    * strength;
+            transform.translation = shake.origin + offset.extend(0.);
+        }
+    }
+
+    /// Move particles under gravity, fading them out over their lifetime
+    fn move_particles(
+        mut commands: Commands,
+        time: Res<Time>,
+        boards: Query<&Board>,
+        mut particles: Query<(
+            Entity,
+            &Parent,
+            &mut Particle,
+            &mut Sprite,
+            &mut Transform,
+        )>,
+    ) {
+        let delta = time.delta_seconds();
+
+        for (entity, parent, mut particle, mut sprite, mut transform) in
+            particles.iter_mut()
+        {
+            if particle.timer.tick(time.delta()).finished() {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+
+            let tile_size =
+                boards.get(parent.get()).map_or(1., |b| b.tile_size);
+            particle.velocity.y -= PARTICLE_GRAVITY * tile_size * delta;
+            transform.translation += particle.velocity * delta;
+            sprite.color.set_a(particle.timer.percent_left());
+        }
+    }
+}
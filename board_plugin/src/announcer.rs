@@ -0,0 +1,219 @@
+use crate::components::{BoardCursor, BoardId, BoardInput};
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, CascadeEvent,
+    TileMarkChangedEvent, TileUncoveredEvent,
+};
+use crate::resources::{tile::TileMark, Board};
+use crate::systems;
+use crate::PauseState;
+use bevy::log;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::sync::{Arc, Mutex};
+
+/// Receiver of the text announcements, a screen reader bridge for instance
+pub trait Announcer: Send + Sync + 'static {
+    fn announce(&mut self, message: &str);
+}
+
+/// Announces through the `info` log
+#[derive(Debug, Default, Copy, Clone)]
+pub struct LogAnnouncer;
+
+impl Announcer for LogAnnouncer {
+    fn announce(&mut self, message: &str) {
+        log::info!("{message}");
+    }
+}
+
+/// Announces on the standard output, one message per line
+#[derive(Debug, Default, Copy, Clone)]
+pub struct StdoutAnnouncer;
+
+impl Announcer for StdoutAnnouncer {
+    fn announce(&mut self, message: &str) {
+        println!("{message}");
+    }
+}
+
+/// Keeps the announcements, clones sharing them so that one can be handed to
+/// the app and another read back by the caller
+#[derive(Debug, Default, Clone)]
+pub struct RecordingAnnouncer(Arc<Mutex<Vec<String>>>);
+
+impl RecordingAnnouncer {
+    /// Take the announcements made so far
+    pub fn take(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|mut messages| std::mem::take(&mut *messages))
+            .unwrap_or_default()
+    }
+}
+
+impl Announcer for RecordingAnnouncer {
+    fn announce(&mut self, message: &str) {
+        if let Ok(mut messages) = self.0.lock() {
+            messages.push(message.to_string());
+        }
+    }
+}
+
+/// Announcer of the board events, a [`LogAnnouncer`] unless inserted before
+/// the plugin is built
+#[derive(Resource)]
+pub struct BoardAnnouncer(pub Box<dyn Announcer>);
+
+impl BoardAnnouncer {
+    pub fn new(announcer: impl Announcer) -> Self {
+        Self(Box::new(announcer))
+    }
+
+    pub fn announce(&mut self, message: &str) {
+        self.0.announce(message);
+    }
+}
+
+/// Description keys
+#[derive(Debug, Clone, Resource)]
+pub struct AnnouncerOptions {
+    /// Describe the tiles under the player cursors
+    pub describe_tile_key: Option<KeyCode>,
+    /// Describe every board row by row
+    pub describe_board_key: Option<KeyCode>,
+}
+
+impl Default for AnnouncerOptions {
+    fn default() -> Self {
+        Self {
+            describe_tile_key: Some(KeyCode::Slash),
+            describe_board_key: Some(KeyCode::Period),
+        }
+    }
+}
+
+/// Text accessibility layer: announces reveals, cascades, marks and game
+/// outcomes, and describes the board on demand
+pub struct AnnouncerPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for AnnouncerPlugin<T> {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<BoardAnnouncer>() {
+            app.insert_resource(BoardAnnouncer::new(LogAnnouncer));
+        }
+
+        app.init_resource::<AnnouncerOptions>().add_systems(
+            Update,
+            (
+                Self::announce_events
+                    .after(systems::uncover::report_cascades)
+                    .after(systems::mark::mark_tiles),
+                Self::describe_on_key,
+            )
+                .run_if(in_state(self.running_state.clone()))
+                .run_if(in_state(PauseState::Running)),
+        );
+
+        log::info!("Loaded Announcer Plugin");
+    }
+}
+
+impl<T> AnnouncerPlugin<T> {
+    /// Announce the board events of this frame, cascades once they stopped
+    /// spreading rather than tile by tile
+    fn announce_events(
+        mut announcer: ResMut<BoardAnnouncer>,
+        boards: Query<(&BoardId, &Board)>,
+        mut tile_uncovered_event_reader: EventReader<TileUncoveredEvent>,
+        mut cascade_event_reader: EventReader<CascadeEvent>,
+        mut tile_mark_changed_event_reader: EventReader<TileMarkChangedEvent>,
+        mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+        mut board_completed_event_reader: EventReader<BoardCompletedEvent>,
+    ) {
+        let reveals = tile_uncovered_event_reader.iter().filter(|e| !e.cascade);
+        for event in reveals {
+            let Some((_, board)) =
+                boards.iter().find(|(id, _)| **id == event.board)
+            else {
+                continue;
+            };
+            let label = board.tile_label(&event.coordinates);
+            announcer
+                .announce(&format!("Revealed {}: {label}", event.coordinates));
+        }
+        for event in cascade_event_reader.iter() {
+            announcer
+                .announce(&format!("Cascade revealed {} tiles", event.tiles));
+        }
+
+        for event in tile_mark_changed_event_reader.iter() {
+            let coords = event.coordinates;
+            let message = match event.mark {
+                TileMark::Flag(1) => format!("Flagged {coords}"),
+                TileMark::Flag(flags) => format!("{flags} flags on {coords}"),
+                TileMark::Question => format!("Question mark on {coords}"),
+                TileMark::None => format!("Unmarked {coords}"),
+            };
+            announcer.announce(&message);
+        }
+
+        for event in bomb_explosion_event_reader.iter() {
+            announcer.announce(&format!(
+                "Mine hit after {:.0} seconds, game lost",
+                event.elapsed.as_secs_f32()
+            ));
+        }
+        for event in board_completed_event_reader.iter() {
+            announcer.announce(&format!(
+                "Board cleared with {} mines in {:.0} seconds, game won",
+                event.bombs,
+                event.elapsed.as_secs_f32()
+            ));
+        }
+    }
+
+    /// Describe the tiles under the cursors or the whole boards on key press
+    fn describe_on_key(
+        mut announcer: ResMut<BoardAnnouncer>,
+        options: Res<AnnouncerOptions>,
+        keys: Res<Input<KeyCode>>,
+        window_query: Query<&Window, With<PrimaryWindow>>,
+        boards: Query<&Board>,
+        cursors: Query<(&Parent, &BoardInput), With<BoardCursor>>,
+    ) {
+        let pressed =
+            |key: Option<KeyCode>| key.is_some_and(|k| keys.just_pressed(k));
+
+        if pressed(options.describe_tile_key) {
+            let window = window_query.get_single().ok();
+            for (parent, input) in cursors.iter() {
+                let Ok(board) = boards.get(parent.get()) else {
+                    continue;
+                };
+                let coordinates = match input {
+                    BoardInput::Mouse => {
+                        window.and_then(|window| board.mouse_position(window))
+                    }
+                    BoardInput::Keyboard(cursor) => Some(cursor.position),
+                };
+                if let Some(coordinates) = coordinates {
+                    announcer.announce(&board.describe_tile(&coordinates));
+                }
+            }
+        }
+
+        if pressed(options.describe_board_key) {
+            for board in boards.iter() {
+                announcer.announce(&format!(
+                    "{} mines left to flag",
+                    board.remaining_bombs()
+                ));
+                for row in board.describe_rows() {
+                    announcer.announce(&row);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, ChordEvent, TileMarkChangedEvent,
+    TileUncoveredEvent,
+};
+use crate::resources::{
+    tile::TileMark, AudioPreferences, BoardSounds, Preferences,
+};
+use crate::systems;
+use bevy::audio::Volume;
+use bevy::log;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Minimum time between two cascade sounds
+const CASCADE_SOUND_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Board sound effects, played from [`BoardSounds`] at the volume set in the
+/// user [`Preferences`]
+pub struct BoardAudioPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for BoardAudioPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            Self::play_sounds
+                .after(systems::uncover::uncover_tiles)
+                .after(systems::mark::mark_tiles)
+                .run_if(in_state(self.running_state.clone()))
+                .run_if(resource_exists::<BoardSounds>()),
+        );
+
+        log::info!("Loaded Board Audio Plugin");
+    }
+}
+
+impl<T> BoardAudioPlugin<T> {
+    /// Play the sounds of this frame's board events, each sound at most once
+    ///
+    /// Cascades only get a sound every [`CASCADE_SOUND_INTERVAL`], and none
+    /// at all when a click or chord sound already plays.
+    #[allow(clippy::too_many_arguments)]
+    fn play_sounds(
+        mut commands: Commands,
+        time: Res<Time>,
+        sounds: Res<BoardSounds>,
+        preferences: Option<Res<Preferences>>,
+        mut last_cascade: Local<Option<Duration>>,
+        mut tile_uncovered_event_reader: EventReader<TileUncoveredEvent>,
+        mut tile_mark_changed_event_reader: EventReader<TileMarkChangedEvent>,
+        mut chord_event_reader: EventReader<ChordEvent>,
+        mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+        mut board_completed_event_reader: EventReader<BoardCompletedEvent>,
+    ) {
+        let mut queued: Vec<&Handle<AudioSource>> = Vec::new();
+        let mut queue = |sound| {
+            if !queued.contains(&sound) {
+                queued.push(sound);
+            }
+        };
+
+        let (cascades, reveals): (
+            Vec<&TileUncoveredEvent>,
+            Vec<&TileUncoveredEvent>,
+        ) = tile_uncovered_event_reader
+            .iter()
+            .partition(|event| event.cascade);
+        if !reveals.is_empty() {
+            queue(&sounds.reveal);
+        }
+
+        let chorded = !chord_event_reader.is_empty();
+        chord_event_reader.clear();
+        if chorded {
+            queue(&sounds.chord);
+        }
+
+        let now = time.elapsed();
+        let cascade_ready = last_cascade
+            .is_none_or(|last| now - last >= CASCADE_SOUND_INTERVAL);
+        if !cascades.is_empty()
+            && reveals.is_empty()
+            && !chorded
+            && cascade_ready
+        {
+            *last_cascade = Some(now);
+            queue(&sounds.cascade);
+        }
+
+        for event in tile_mark_changed_event_reader.iter() {
+            match event.mark {
+                TileMark::Flag(_) => queue(&sounds.flag),
+                TileMark::Question | TileMark::None => queue(&sounds.unflag),
+            }
+        }
+
+        if !bomb_explosion_event_reader.is_empty() {
+            bomb_explosion_event_reader.clear();
+            queue(&sounds.explosion);
+        }
+        if !board_completed_event_reader.is_empty() {
+            board_completed_event_reader.clear();
+            queue(&sounds.win);
+        }
+
+        let volume = preferences.map_or_else(
+            || AudioPreferences::default().effective_volume(),
+            |p| p.audio.effective_volume(),
+        );
+        if volume <= 0. {
+            return;
+        }
+
+        for sound in queued {
+            commands.spawn(AudioBundle {
+                source: sound.clone(),
+                settings: PlaybackSettings::DESPAWN
+                    .with_volume(Volume::new_relative(volume)),
+            });
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use crate::resources::{InputAction, InputBinding, InputMap};
+use bevy::ecs::system::SystemParam;
+use bevy::log;
+use bevy::prelude::*;
+use std::fmt::Write;
+
+/// Action state read through the [`InputMap`]
+///
+/// Actions are never pressed while the rebinding screen is open.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    input_map: Res<'w, InputMap>,
+    keys: Res<'w, Input<KeyCode>>,
+    mouse_buttons: Res<'w, Input<MouseButton>>,
+    /// Missing without gamepad support
+    gamepad_buttons: Option<Res<'w, Input<GamepadButton>>>,
+    gamepads: Option<Res<'w, Gamepads>>,
+    rebinding: Option<Res<'w, Rebinding>>,
+}
+
+impl ActionInput<'_> {
+    /// Check `button` on every connected gamepad with `check`
+    fn gamepad_button(
+        &self,
+        button: GamepadButtonType,
+        check: impl Fn(&Input<GamepadButton>, GamepadButton) -> bool,
+    ) -> bool {
+        let (Some(gamepads), Some(buttons)) =
+            (&self.gamepads, &self.gamepad_buttons)
+        else {
+            return false;
+        };
+        gamepads
+            .iter()
+            .any(|gamepad| check(buttons, GamepadButton::new(gamepad, button)))
+    }
+
+    /// Was a binding of `action` pressed this frame
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.rebinding.is_none()
+            && self.input_map.bindings(action).iter().any(|binding| {
+                match *binding {
+                    InputBinding::Key(key) => self.keys.just_pressed(key),
+                    InputBinding::Mouse(button) => {
+                        self.mouse_buttons.just_pressed(button)
+                    }
+                    InputBinding::Gamepad(button) => {
+                        self.gamepad_button(button, Input::just_pressed)
+                    }
+                }
+            })
+    }
+
+    /// Was a binding of `action` released this frame
+    pub fn just_released(&self, action: InputAction) -> bool {
+        self.rebinding.is_none()
+            && self.input_map.bindings(action).iter().any(|binding| {
+                match *binding {
+                    InputBinding::Key(key) => self.keys.just_released(key),
+                    InputBinding::Mouse(button) => {
+                        self.mouse_buttons.just_released(button)
+                    }
+                    InputBinding::Gamepad(button) => {
+                        self.gamepad_button(button, Input::just_released)
+                    }
+                }
+            })
+    }
+}
+
+/// Rebinding screen settings
+#[derive(Debug, Clone, Resource)]
+pub struct BindingsOptions {
+    /// Key opening and closing the rebinding screen
+    pub screen_key: Option<KeyCode>,
+}
+
+impl Default for BindingsOptions {
+    fn default() -> Self {
+        Self {
+            screen_key: Some(KeyCode::F1),
+        }
+    }
+}
+
+/// State of the open rebinding screen
+#[derive(Debug, Default, Resource)]
+pub struct Rebinding {
+    /// Selected action, index in [`InputAction::ALL`]
+    selected: usize,
+    /// Waiting for the new binding of the selected action
+    listening: bool,
+}
+
+/// Root node of the rebinding screen
+#[derive(Debug, Component)]
+struct BindingsScreen;
+
+/// Text of the rebinding screen
+#[derive(Debug, Component)]
+struct BindingsText;
+
+/// Rebinding screen, listing the actions of the [`InputMap`] and binding
+/// them to the next pressed key or button
+pub struct BindingsPlugin;
+
+impl Plugin for BindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BindingsOptions>()
+            .init_resource::<InputMap>()
+            .add_systems(
+                Update,
+                (
+                    Self::toggle_screen,
+                    Self::rebind.run_if(resource_exists::<Rebinding>()),
+                    Self::update_screen,
+                )
+                    .chain(),
+            );
+
+        log::info!("Loaded Bindings Plugin");
+    }
+}
+
+impl BindingsPlugin {
+    /// Open or close the rebinding screen, kept open while listening
+    fn toggle_screen(
+        mut commands: Commands,
+        options: Res<BindingsOptions>,
+        keys: Res<Input<KeyCode>>,
+        rebinding: Option<Res<Rebinding>>,
+        screens: Query<Entity, With<BindingsScreen>>,
+    ) {
+        if !options.screen_key.is_some_and(|key| keys.just_pressed(key)) {
+            return;
+        }
+
+        match rebinding {
+            Some(rebinding) if rebinding.listening => (),
+            Some(_) => {
+                commands.remove_resource::<Rebinding>();
+                for entity in screens.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            None => {
+                commands.init_resource::<Rebinding>();
+                commands
+                    .spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(10.),
+                            right: Val::Px(10.),
+                            padding: UiRect::all(Val::Px(10.)),
+                            ..default()
+                        },
+                        background_color: Color::rgba(0., 0., 0., 0.75).into(),
+                        ..default()
+                    })
+                    .insert(Name::new("Bindings Screen"))
+                    .insert(BindingsScreen)
+                    .with_children(|parent| {
+                        parent
+                            .spawn(TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 14.,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ))
+                            .insert(BindingsText);
+                    });
+            }
+        }
+    }
+
+    /// Select actions with the arrow keys, `Return` to listen for a new
+    /// binding, `Back` to restore the default ones
+    fn rebind(
+        mut rebinding: ResMut<Rebinding>,
+        mut input_map: ResMut<InputMap>,
+        options: Res<BindingsOptions>,
+        keys: Res<Input<KeyCode>>,
+        mouse_buttons: Res<Input<MouseButton>>,
+        gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    ) {
+        let action = InputAction::ALL[rebinding.selected];
+
+        if !rebinding.listening {
+            let count = InputAction::ALL.len();
+            if keys.just_pressed(KeyCode::Down) {
+                rebinding.selected = (rebinding.selected + 1) % count;
+            }
+            if keys.just_pressed(KeyCode::Up) {
+                rebinding.selected = (rebinding.selected + count - 1) % count;
+            }
+            if keys.just_pressed(KeyCode::Return) {
+                rebinding.listening = true;
+            }
+            if keys.just_pressed(KeyCode::Back) {
+                *input_map.bindings_mut(action) =
+                    InputMap::default().bindings(action).to_vec();
+            }
+            return;
+        }
+
+        // The screen key cancels, every other key can be bound
+        if options.screen_key.is_some_and(|key| keys.just_pressed(key)) {
+            rebinding.listening = false;
+            return;
+        }
+        let binding = keys
+            .get_just_pressed()
+            .next()
+            .map(|key| InputBinding::Key(*key))
+            .or_else(|| {
+                let button = mouse_buttons.get_just_pressed().next()?;
+                Some(InputBinding::Mouse(*button))
+            })
+            .or_else(|| {
+                let button =
+                    gamepad_buttons.as_ref()?.get_just_pressed().next()?;
+                Some(InputBinding::Gamepad(button.button_type))
+            });
+
+        if let Some(binding) = binding {
+            log::info!("{action} bound to {binding}");
+            input_map.rebind(action, binding);
+            rebinding.listening = false;
+        }
+    }
+
+    /// Rewrite the screen text when the bindings or the selection change
+    fn update_screen(
+        options: Res<BindingsOptions>,
+        input_map: Res<InputMap>,
+        rebinding: Option<Res<Rebinding>>,
+        mut texts: Query<&mut Text, With<BindingsText>>,
+    ) {
+        let Some(rebinding) = rebinding else {
+            return;
+        };
+        let Ok(mut text) = texts.get_single_mut() else {
+            return;
+        };
+        if !input_map.is_changed()
+            && !rebinding.is_changed()
+            && !text.sections[0].value.is_empty()
+        {
+            return;
+        }
+
+        let mut value = String::new();
+        let close = options
+            .screen_key
+            .map_or_else(String::new, |key| format!(" ({key:?} to close)"));
+        let _ = writeln!(value, "Bindings{close}");
+        let _ = writeln!(value, "Up/Down select, Return rebind, Back reset");
+        for (i, action) in InputAction::ALL.iter().enumerate() {
+            let cursor = if i == rebinding.selected { ">" } else { " " };
+            let bindings = if i == rebinding.selected && rebinding.listening {
+                let cancel =
+                    options.screen_key.map_or_else(String::new, |key| {
+                        format!(" ({key:?} to cancel)")
+                    });
+                format!("press a key or button{cancel}")
+            } else {
+                let bindings: Vec<_> = input_map
+                    .bindings(*action)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                bindings.join(", ")
+            };
+            let _ = writeln!(value, "{cursor} {action}: {bindings}");
+        }
+        text.sections[0].value = value;
+    }
+}
@@ -1,4 +1,6 @@
-use bevy::prelude::Vec2;
+use crate::components::Coordinates;
+use bevy::log;
+use bevy::prelude::{Vec2, Window};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Bounds2 {
@@ -15,4 +17,31 @@ impl Bounds2 {
             && pos.x <= self.position.x + self.size.x
             && pos.y <= self.position.y + self.size.y
     }
+
+    /// Translate the window cursor position to the coordinates of a tile
+    /// of `tile_size` inside of the bounds
+    pub fn cursor_coordinates(
+        &self,
+        window: &Window,
+        tile_size: f32,
+    ) -> Option<Coordinates> {
+        let position = window.cursor_position()?;
+        log::trace!("Mouse position: {}", position);
+
+        let window_size = Vec2::new(window.width(), window.height());
+        let position = position - window_size / 2.;
+        log::trace!("Adjusted position: {}", position);
+
+        if !self.in_bounds(position) {
+            return None;
+        }
+        log::trace!("In bounds {:?}", self);
+
+        let coordinates = position - self.position;
+
+        Some(Coordinates {
+            x: (coordinates.x / tile_size) as u16,
+            y: (coordinates.y / tile_size) as u16,
+        })
+    }
 }
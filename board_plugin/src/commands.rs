@@ -0,0 +1,211 @@
+use crate::components::{BoardId, Coordinates, PlayerId};
+use crate::events::{TileMarkEvent, TileTriggerEvent};
+use crate::resources::{
+    Board, BoardAssets, BoardOptions, BoardPosition, GameStats, PlayerOptions,
+    PlayerStats, Replay, TileMask,
+};
+use crate::BoardPlugin;
+use bevy::ecs::system::Command;
+use bevy::log;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use rand::{thread_rng, Rng};
+
+/// Uncover a tile, as if `player` clicked it
+///
+/// Revealed numbers are chorded. The reveal is processed by
+/// [`BoardSet::Uncover`](crate::BoardSet::Uncover) like any player input.
+#[derive(Debug, Copy, Clone)]
+pub struct RevealTile {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+}
+
+impl Command for RevealTile {
+    fn apply(self, world: &mut World) {
+        world.send_event(TileTriggerEvent {
+            board: self.board,
+            player: self.player,
+            coordinates: self.coordinates,
+        });
+    }
+}
+
+/// Toggle the mark of a tile, as if `player` right clicked it
+///
+/// Covered tiles are flagged, then unmarked or question marked depending on
+/// the board options.
+#[derive(Debug, Copy, Clone)]
+pub struct FlagTile {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+}
+
+impl Command for FlagTile {
+    fn apply(self, world: &mut World) {
+        world.send_event(TileMarkEvent {
+            board: self.board,
+            player: self.player,
+            coordinates: self.coordinates,
+        });
+    }
+}
+
+/// Replace a board with a new one, generated from the options it was
+/// spawned with
+///
+/// Other boards are left untouched. Unlike leaving the running state, the
+/// host app stays where it is.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct RestartBoard {
+    pub board: BoardId,
+}
+
+impl Command for RestartBoard {
+    fn apply(self, world: &mut World) {
+        run_system(
+            world,
+            move |mut commands: Commands,
+                  boards: Query<(
+                Entity,
+                &BoardId,
+                &BoardOptions,
+                &Board,
+                &Transform,
+            )>,
+                  board_assets: Res<BoardAssets>,
+                  mut game_stats: ResMut<GameStats>| {
+                let Some((entity, _, options, board, transform)) =
+                    boards.iter().find(|(_, id, ..)| **id == self.board)
+                else {
+                    log::error!("Board {} not found.", self.board);
+                    return;
+                };
+                commands.entity(entity).despawn_recursive();
+
+                // Stored options hold the resolved shape of the board
+                let seed = options.seed.unwrap_or_else(|| thread_rng().gen());
+                let tile_map = options.tile_map(seed);
+
+                BoardPlugin::<()>::spawn_board(
+                    &mut commands,
+                    self.board,
+                    options,
+                    &tile_map,
+                    board.tile_size,
+                    transform.translation,
+                    &board.players,
+                    &board_assets,
+                );
+
+                if boards.iter().count() == 1 {
+                    let mut stats =
+                        GameStats::new(options.style(), board.players.len());
+                    stats.add_board(self.board, tile_map.bbbv());
+                    *game_stats = stats;
+                    commands.insert_resource(PlayerStats::default());
+                    commands
+                        .insert_resource(Replay::new(options.clone(), seed));
+                } else {
+                    game_stats.add_board(self.board, tile_map.bbbv());
+                }
+
+                log::info!("Board {} restarted", self.board);
+            },
+        );
+    }
+}
+
+/// Spawn a board alongside the running ones, with its own options
+///
+/// Boards are independent: events and commands target them by `id`, which
+/// must not be taken. Game and player stats are shared by every board, the
+/// game going on until each of them ended. Replays only cover single board
+/// games, so the running one is dropped when joined by another board.
+#[derive(Debug, Clone)]
+pub struct SpawnBoard {
+    pub id: BoardId,
+    pub options: BoardOptions,
+    /// Players of the board, identified by their index
+    pub players: Vec<PlayerOptions>,
+}
+
+impl Command for SpawnBoard {
+    fn apply(self, world: &mut World) {
+        run_system(
+            world,
+            move |mut commands: Commands,
+                  boards: Query<&BoardId, With<Board>>,
+                  board_assets: Res<BoardAssets>,
+                  masks: Res<Assets<TileMask>>,
+                  mut game_stats: ResMut<GameStats>,
+                  window_query: Query<&Window, With<PrimaryWindow>>| {
+                if boards.iter().any(|id| *id == self.id) {
+                    log::error!("Board {} already exists.", self.id);
+                    return;
+                }
+
+                let mut options = self.options.clone();
+                options.shape.resolve(&masks);
+                let seed = options.seed.unwrap_or_else(|| thread_rng().gen());
+                let tile_map = options.tile_map(seed);
+
+                // Adaptive tiles get their minimum size without a window
+                let available = window_query
+                    .get_single()
+                    .map_or(Vec2::ZERO, |w| Vec2::new(w.width(), w.height()));
+                let tile_size = BoardPlugin::<()>::board_tile_size(
+                    &options, &tile_map, available,
+                );
+                let board_size = Vec2::new(
+                    tile_map.width() as f32 * tile_size,
+                    tile_map.height() as f32 * tile_size,
+                );
+                let board_position = match options.position {
+                    BoardPosition::Centered { offset } => {
+                        (-board_size / 2.).extend(0.) + offset
+                    }
+                    BoardPosition::Custom(p) => p,
+                };
+
+                let mut players: Vec<_> = self
+                    .players
+                    .iter()
+                    .enumerate()
+                    .map(|(i, player)| (PlayerId(i as u8), player.clone()))
+                    .collect();
+                if players.is_empty() {
+                    players.push((PlayerId(0), PlayerOptions::default()));
+                }
+
+                BoardPlugin::<()>::spawn_board(
+                    &mut commands,
+                    self.id,
+                    &options,
+                    &tile_map,
+                    tile_size,
+                    board_position,
+                    &players,
+                    &board_assets,
+                );
+                game_stats.players += players.len();
+                game_stats.add_board(self.id, tile_map.bbbv());
+                if !boards.is_empty() {
+                    commands.remove_resource::<Replay>();
+                }
+
+                log::info!("Board {} spawned", self.id);
+            },
+        );
+    }
+}
+
+/// Run `system` once on `world`, applying its commands
+fn run_system<M>(world: &mut World, system: impl IntoSystem<(), (), M>) {
+    let mut system = IntoSystem::into_system(system);
+    system.initialize(world);
+    system.run((), world);
+    system.apply_deferred(world);
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use std::fmt::{self, Display, Formatter};
+
+/// Identifier of a board entity, carried by every board event
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+)]
+pub struct BoardId(pub u8);
+
+impl Display for BoardId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
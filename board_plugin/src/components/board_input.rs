@@ -0,0 +1,37 @@
+use crate::components::Coordinates;
+use bevy::prelude::*;
+
+/// Input device of a player cursor
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Component)]
+pub enum BoardInput {
+    /// Mouse clicks on the board
+    #[default]
+    Mouse,
+    /// Keyboard controlled cursor
+    Keyboard(KeyboardCursor),
+}
+
+/// Keyboard controlled board cursor
+///
+/// Every keyboard cursor is driven by the cursor actions of the
+/// [`InputMap`](crate::resources::InputMap).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct KeyboardCursor {
+    /// Current cursor position
+    pub position: Coordinates,
+}
+
+/// Player cursor on a board, child of the board entity
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+)]
+pub struct BoardCursor;
+
+/// Mouse cursor holding the reveal button down since a press on its board,
+/// the tile under it is revealed on release
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+)]
+pub struct MousePress;
@@ -0,0 +1,49 @@
+use crate::resources::SkinTile;
+use bevy::prelude::*;
+
+/// Visual role of a board entity, its look is rebuilt from the board assets
+/// whenever they or the role change
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(Debug, Copy, Clone, PartialEq, Component)]
+pub enum BoardSprite {
+    /// Board background
+    Background,
+    /// Uncovered tile
+    Tile,
+    Cover,
+    /// Cover held down by a mouse button, before the reveal on release
+    PressedCover,
+    Flag {
+        /// Color of the player who placed it, on shared boards
+        tint: Option<Color>,
+        /// Placed on a safe tile, shown once the game is lost
+        wrong: bool,
+    },
+    Question,
+    Bomb {
+        exploded: bool,
+    },
+    /// Bomb counter of a tile, drawn by the skin up to 8
+    Counter(u8),
+    /// Bomb or flag count of a multi-bomb tile, always drawn as text
+    Count(u8),
+}
+
+impl BoardSprite {
+    /// Skin cell drawing the role, if any
+    #[must_use]
+    pub fn skin_tile(self) -> Option<SkinTile> {
+        match self {
+            Self::Background | Self::Count(_) => None,
+            Self::Tile => Some(SkinTile::Uncovered),
+            Self::Cover => Some(SkinTile::Covered),
+            Self::PressedCover => Some(SkinTile::Pressed),
+            Self::Flag { wrong: false, .. } => Some(SkinTile::Flag),
+            Self::Flag { wrong: true, .. } => Some(SkinTile::WrongFlag),
+            Self::Question => Some(SkinTile::Question),
+            Self::Bomb { exploded: false } => Some(SkinTile::Mine),
+            Self::Bomb { exploded: true } => Some(SkinTile::ExplodedMine),
+            Self::Counter(n) => Some(SkinTile::Number(n)),
+        }
+    }
+}
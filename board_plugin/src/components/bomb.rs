@@ -2,4 +2,7 @@ use bevy::prelude::*;
 
 #[cfg_attr(feature = "debug", derive(Reflect))]
 #[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
-pub struct Bomb;
+pub struct Bomb {
+    /// Number of bombs held by the tile
+    pub count: u8,
+}
@@ -1,10 +1,22 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use std::ops::{Add, Sub};
 
 #[cfg_attr(feature = "debug", derive(Reflect))]
 #[derive(
-    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Hash,
+    Component,
+    Serialize,
+    Deserialize,
 )]
 pub struct Coordinates {
     pub x: u16,
@@ -15,6 +27,21 @@ impl Coordinates {
     pub fn new(x: u16, y: u16) -> Self {
         Self { x, y }
     }
+
+    /// Offset by `(x, y)`, wrapping around a `width` x `height` area
+    pub fn wrapping_add(
+        self,
+        (x, y): (i8, i8),
+        (width, height): (u16, u16),
+    ) -> Self {
+        let x = (self.x as i32 + x as i32).rem_euclid(width.max(1) as i32);
+        let y = (self.y as i32 + y as i32).rem_euclid(height.max(1) as i32);
+
+        Self {
+            x: x as u16,
+            y: y as u16,
+        }
+    }
 }
 
 impl From<(u16, u16)> for Coordinates {
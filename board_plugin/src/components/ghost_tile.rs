@@ -0,0 +1,12 @@
+use crate::components::Coordinates;
+use bevy::prelude::*;
+
+/// Edge hint mirroring a tile on the opposite side of a wrapping board
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+)]
+pub struct GhostTile {
+    /// Coordinates of the mirrored tile
+    pub source: Coordinates,
+}
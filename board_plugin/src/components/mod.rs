@@ -1,9 +1,21 @@
+pub use board_id::BoardId;
+pub use board_input::{BoardCursor, BoardInput, KeyboardCursor, MousePress};
+pub use board_sprite::BoardSprite;
 pub use bomb::Bomb;
 pub use bomb_neighbor::BombNeighbor;
 pub use coordinates::Coordinates;
+pub use ghost_tile::GhostTile;
+pub use player_id::PlayerId;
+pub use reveal_origin::RevealOrigin;
 pub use uncover::Uncover;
 
+mod board_id;
+mod board_input;
+mod board_sprite;
 mod bomb;
 mod bomb_neighbor;
 mod coordinates;
+mod ghost_tile;
+mod player_id;
+mod reveal_origin;
 mod uncover;
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use std::fmt::{self, Display, Formatter};
+
+/// Identifier of a player, carried by cursors and tile actions
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+)]
+pub struct PlayerId(pub u8);
+
+impl Display for PlayerId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "P{}", self.0 + 1)
+    }
+}
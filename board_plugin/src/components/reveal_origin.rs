@@ -0,0 +1,19 @@
+use crate::components::Coordinates;
+use bevy::prelude::*;
+
+/// Tile that was clicked to start a reveal, carried along its cascade
+#[cfg_attr(feature = "debug", derive(Reflect))]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component,
+)]
+pub struct RevealOrigin(pub Coordinates);
+
+impl RevealOrigin {
+    /// Distance in tiles from the origin to `coordinates`
+    #[must_use]
+    pub fn distance(&self, coordinates: Coordinates) -> f32 {
+        let dx = self.0.x as f32 - coordinates.x as f32;
+        let dy = self.0.y as f32 - coordinates.y as f32;
+        dx.hypot(dy)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::resources::{DailyChallenge, DailyResult, GameStats};
+use crate::systems;
+use bevy::log;
+use bevy::prelude::*;
+
+/// Daily challenge tracking, records the result of boards played while a
+/// [`DailyChallenge`] resource exists
+pub struct DailyChallengePlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for DailyChallengePlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(self.running_state.clone()),
+            Self::announce_challenge
+                .run_if(resource_exists::<DailyChallenge>()),
+        )
+        .add_systems(
+            Update,
+            Self::record_result
+                .after(systems::stats::update_game_stats)
+                .run_if(in_state(self.running_state.clone()))
+                .run_if(resource_exists::<DailyChallenge>()),
+        );
+
+        log::info!("Loaded Daily Challenge Plugin");
+    }
+}
+
+impl<T> DailyChallengePlugin<T> {
+    /// Log the challenge and any result already stored for its date
+    fn announce_challenge(mut challenge: ResMut<DailyChallenge>) {
+        challenge.result = None;
+        log::info!("Daily challenge {}", challenge.date);
+
+        match DailyResult::load_all(&challenge.results_path) {
+            Ok(results) => {
+                let previous = results
+                    .iter()
+                    .filter(|result| result.date == challenge.date);
+                for result in previous {
+                    log::info!("Previous attempt:\n{}", result.summary());
+                }
+            }
+            Err(e) => log::error!("Failed to load daily results: {e}"),
+        }
+    }
+
+    /// Store the result once the game is over
+    fn record_result(
+        game_stats: Res<GameStats>,
+        mut challenge: ResMut<DailyChallenge>,
+    ) {
+        if challenge.result.is_some() {
+            return;
+        }
+        let Some(result) = challenge.result(&game_stats) else {
+            return;
+        };
+
+        log::info!("{}", result.summary());
+        if let Err(e) = result.save(&challenge.results_path) {
+            log::error!("Failed to save daily result: {e}");
+        }
+        challenge.result = Some(result);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::resources::{
+    BoardAssets, GameOutcome, GameStats, PlayerStats, StatsHistory,
+};
+use bevy::log;
+use bevy::prelude::*;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Stats storage options
+#[derive(Debug, Clone, Resource)]
+pub struct StatsOptions {
+    /// Local stats file
+    pub path: PathBuf,
+}
+
+impl Default for StatsOptions {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("stats.json"),
+        }
+    }
+}
+
+/// Root node of the end of game screen
+#[derive(Debug, Component)]
+struct EndScreen;
+
+/// End of game screen, finished games are added to the stats history
+pub struct EndScreenPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for EndScreenPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatsOptions>()
+            .add_systems(Startup, Self::load_history)
+            .add_systems(
+                Update,
+                Self::show_end_screen
+                    .run_if(in_state(self.running_state.clone())),
+            )
+            .add_systems(
+                OnExit(self.running_state.clone()),
+                Self::cleanup_end_screen,
+            );
+
+        log::info!("Loaded End Screen Plugin");
+    }
+}
+
+impl<T> EndScreenPlugin<T> {
+    fn load_history(mut commands: Commands, options: Res<StatsOptions>) {
+        match StatsHistory::load(&options.path) {
+            Ok(history) => commands.insert_resource(history),
+            Err(e) => {
+                log::error!("Failed to load stats: {e}");
+                commands.insert_resource(StatsHistory::default());
+            }
+        }
+    }
+
+    /// Record the game and display its stats once it is over
+    fn show_end_screen(
+        mut commands: Commands,
+        game_stats: Res<GameStats>,
+        player_stats: Res<PlayerStats>,
+        options: Res<StatsOptions>,
+        mut history: ResMut<StatsHistory>,
+        board_assets: Res<BoardAssets>,
+        screens: Query<(), With<EndScreen>>,
+    ) {
+        if game_stats.outcome.is_none() || !screens.is_empty() {
+            return;
+        }
+
+        // Race and coop games aren't comparable with solo ones
+        if game_stats.is_solo() {
+            history.record(&game_stats);
+            if let Err(e) = history.save(&options.path) {
+                log::error!("Failed to save stats: {e}");
+            }
+        }
+
+        let text = Self::summary(&game_stats, &player_stats, &history);
+        log::info!("{text}");
+
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.),
+                    left: Val::Px(10.),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.75).into(),
+                ..default()
+            })
+            .insert(Name::new("End Screen"))
+            .insert(EndScreen)
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font: board_assets.bomb_counter_font.clone(),
+                        font_size: 14.,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+    }
+
+    /// Text of the end screen
+    fn summary(
+        game: &GameStats,
+        players: &PlayerStats,
+        history: &StatsHistory,
+    ) -> String {
+        let mut text = String::new();
+        let clicks = &game.clicks;
+        let title = match game.outcome {
+            Some(GameOutcome::Won) => "Cleared!",
+            _ => "Boom!",
+        };
+
+        let _ = writeln!(text, "{title} ({} play)", game.style);
+        let _ = writeln!(text, "Time {:.1}s", game.elapsed.as_secs_f32());
+        let _ = writeln!(
+            text,
+            "3BV {} ({:.2}/s)",
+            game.bbbv,
+            game.bbbv_per_second()
+        );
+        let _ = writeln!(
+            text,
+            "Clicks {} left, {} right, {} chord, {} wasted",
+            clicks.left, clicks.right, clicks.chord, clicks.wasted
+        );
+        let _ = writeln!(text, "Efficiency {:.0}%", game.efficiency() * 100.);
+
+        // Contributions are only relevant with several players
+        let players: Vec<_> = players.iter().collect();
+        if players.len() > 1 {
+            for (player, stats) in players {
+                let _ = writeln!(
+                    text,
+                    "{player}: {} tiles, {} flags",
+                    stats.tiles_uncovered, stats.flags_placed
+                );
+            }
+        }
+
+        // Boards of a race end on their own
+        if game.boards().count() > 1 {
+            for (id, board) in game.boards() {
+                let outcome = match board.outcome {
+                    Some(GameOutcome::Won) => "cleared",
+                    _ => "boom",
+                };
+                let _ = writeln!(
+                    text,
+                    "Board {id}: {outcome} in {:.1}s, {:.0}% efficiency",
+                    board.elapsed.as_secs_f32(),
+                    board.efficiency() * 100.
+                );
+            }
+        }
+
+        let total = history.get(game.style);
+        let _ = write!(
+            text,
+            "{} style: {}/{} won, {:.0}% efficiency (best {:.0}%)",
+            game.style,
+            total.won,
+            total.played,
+            total.efficiency() * 100.,
+            total.best_efficiency * 100.
+        );
+
+        text
+    }
+
+    fn cleanup_end_screen(
+        mut commands: Commands,
+        screens: Query<Entity, With<EndScreen>>,
+    ) {
+        for entity in screens.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
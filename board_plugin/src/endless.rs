@@ -0,0 +1,86 @@
+use crate::events::BombExplosionEvent;
+use crate::resources::{
+    chunk_map::ChunkMap, EndlessBoard, EndlessOptions, InputMap,
+};
+use crate::{systems, PauseState};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use bevy::{log, math::IVec2};
+use std::collections::VecDeque;
+
+/// Endless minesweeper, the board is generated in chunks around the camera
+pub struct EndlessBoardPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for EndlessBoardPlugin<T> {
+    fn build(&self, app: &mut App) {
+        // Shared with the board plugin when both are added
+        if !app.world.contains_resource::<State<PauseState>>() {
+            app.add_state::<PauseState>();
+        }
+        app.add_systems(
+            OnEnter(self.running_state.clone()),
+            Self::create_board,
+        )
+        .add_systems(
+            Update,
+            (
+                systems::endless::update_chunks,
+                systems::endless::handle_input,
+                systems::endless::continue_cascades,
+            )
+                .chain()
+                .run_if(in_state(self.running_state.clone()))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(OnExit(self.running_state.clone()), Self::cleanup_board)
+        .add_event::<BombExplosionEvent>()
+        .init_resource::<InputMap>();
+
+        log::info!("Loaded Endless Board Plugin");
+    }
+}
+
+impl<T> EndlessBoardPlugin<T> {
+    /// system to set up an empty endless board, chunks are spawned lazily
+    pub fn create_board(
+        mut commands: Commands,
+        options: Option<Res<EndlessOptions>>,
+    ) {
+        let options = options.map(|o| o.clone()).unwrap_or_default();
+
+        let entity = commands
+            .spawn(SpatialBundle::default())
+            .insert(Name::new("Endless Board"))
+            .id();
+
+        let mut board = EndlessBoard {
+            chunk_map: ChunkMap::new(
+                options.seed,
+                options.chunk_size,
+                options.bomb_density,
+            ),
+            tile_size: options.tile_size,
+            tile_padding: options.tile_padding,
+            entity,
+            revealed: HashSet::new(),
+            flagged: HashSet::new(),
+            chunk_entities: HashMap::new(),
+            covers: HashMap::new(),
+            pending: VecDeque::new(),
+            lost: false,
+        };
+
+        // The origin is always safe, start the game from it
+        board.reveal(IVec2::ZERO);
+
+        commands.insert_resource(board);
+    }
+
+    fn cleanup_board(board: Res<EndlessBoard>, mut commands: Commands) {
+        log::info!("Endless board score: {}", board.score());
+        commands.entity(board.entity).despawn_recursive();
+        commands.remove_resource::<EndlessBoard>();
+    }
+}
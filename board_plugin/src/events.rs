@@ -1,18 +1,91 @@
-use crate::components::Coordinates;
-use bevy::prelude::Event;
+use crate::components::{BoardId, Coordinates, PlayerId};
+use crate::resources::tile::{Tile, TileMark};
+use bevy::prelude::{Entity, Event};
+use std::time::Duration;
 
 /// Event that occurs when a tile is triggered (left clicked)
 #[derive(Debug, Copy, Clone, Event)]
-pub struct TileTriggerEvent(pub Coordinates);
+pub struct TileTriggerEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+}
 
 /// Event that occurs when the board is completed
 #[derive(Debug, Copy, Clone, Event)]
-pub struct BoardCompletedEvent;
+pub struct BoardCompletedEvent {
+    pub board: BoardId,
+    /// Player who uncovered the last safe tile
+    pub player: PlayerId,
+    /// Game time at completion
+    pub elapsed: Duration,
+    /// Number of bombs of the board
+    pub bombs: u16,
+}
 
 /// Event that occurs when a player uncovers a bomb
 #[derive(Debug, Copy, Clone, Event)]
-pub struct BombExplosionEvent;
+pub struct BombExplosionEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    /// Uncovered bomb, `None` on endless boards which aren't addressed by
+    /// [`Coordinates`]
+    pub coordinates: Option<Coordinates>,
+    /// Game time of the explosion, zero on endless boards which aren't timed
+    pub elapsed: Duration,
+}
 
 /// Event that occurs when a tile is marked (right clicked)
 #[derive(Debug, Copy, Clone, Event)]
-pub struct TileMarkEvent(pub Coordinates);
+pub struct TileMarkEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+}
+
+/// Event that occurs when a tile gets uncovered, for every tile of a cascade
+#[derive(Debug, Copy, Clone, Event)]
+pub struct TileUncoveredEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+    /// Tile entity, parent of the cover
+    pub entity: Entity,
+    pub tile: Tile,
+    /// Tile clicked or chorded to start the reveal
+    pub origin: Coordinates,
+    /// Uncovered as a neighbor of another tile rather than clicked directly
+    pub cascade: bool,
+}
+
+/// Event that occurs once a cascade stopped spreading
+#[derive(Debug, Copy, Clone, Event)]
+pub struct CascadeEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    /// Tile clicked or chorded to start the cascade
+    pub origin: Coordinates,
+    /// Number of tiles uncovered by the cascade, the origin excluded
+    pub tiles: usize,
+}
+
+/// Event that occurs when the mark of a tile changes
+#[derive(Debug, Copy, Clone, Event)]
+pub struct TileMarkChangedEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+    pub mark: TileMark,
+    /// Bombs left to flag on the board
+    pub bombs_left: i32,
+}
+
+/// Event that occurs when a revealed number is chorded
+#[derive(Debug, Copy, Clone, Event)]
+pub struct ChordEvent {
+    pub board: BoardId,
+    pub player: PlayerId,
+    pub coordinates: Coordinates,
+    /// Number of covered neighbors being uncovered
+    pub tiles: usize,
+}
@@ -0,0 +1,189 @@
+use crate::components::Coordinates;
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, CascadeEvent, ChordEvent,
+    TileMarkChangedEvent, TileUncoveredEvent,
+};
+use crate::resources::tile::{Tile, TileMark};
+use crate::systems;
+use bevy::log;
+use bevy::prelude::*;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Game log storage options
+#[derive(Debug, Clone, Resource)]
+pub struct GameLogOptions {
+    /// Log file, entries are appended as JSON lines
+    pub path: PathBuf,
+}
+
+impl Default for GameLogOptions {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("game_log.jsonl"),
+        }
+    }
+}
+
+/// Board event recorded in the game log
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameLogEntry {
+    TileUncovered {
+        board: u8,
+        player: u8,
+        coordinates: Coordinates,
+        tile: Tile,
+        cascade: bool,
+    },
+    Cascade {
+        board: u8,
+        player: u8,
+        origin: Coordinates,
+        tiles: usize,
+    },
+    Chord {
+        board: u8,
+        player: u8,
+        coordinates: Coordinates,
+        tiles: usize,
+    },
+    MarkChanged {
+        board: u8,
+        player: u8,
+        coordinates: Coordinates,
+        mark: TileMark,
+        bombs_left: i32,
+    },
+    BombExplosion {
+        board: u8,
+        player: u8,
+        coordinates: Option<Coordinates>,
+        elapsed_ms: u64,
+    },
+    BoardCompleted {
+        board: u8,
+        player: u8,
+        bombs: u16,
+        elapsed_ms: u64,
+    },
+}
+
+/// Line of the game log
+#[derive(Debug, Clone, Serialize)]
+struct GameLogLine<'a> {
+    /// Time since the app started
+    time_ms: u64,
+    #[serde(flatten)]
+    entry: &'a GameLogEntry,
+}
+
+/// Append `entries` to the log at `path`, one JSON object per line
+fn append_game_log(
+    path: &Path,
+    time_ms: u64,
+    entries: &[GameLogEntry],
+) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        let line = serde_json::to_string(&GameLogLine { time_ms, entry })?;
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Structured log of the board events, for analytics
+pub struct GameLogPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for GameLogPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameLogOptions>().add_systems(
+            Update,
+            Self::write_game_log
+                .after(systems::uncover::report_cascades)
+                .after(systems::mark::mark_tiles)
+                .run_if(in_state(self.running_state.clone())),
+        );
+
+        log::info!("Loaded Game Log Plugin");
+    }
+}
+
+impl<T> GameLogPlugin<T> {
+    #[allow(clippy::too_many_arguments)]
+    fn write_game_log(
+        time: Res<Time>,
+        options: Res<GameLogOptions>,
+        mut tile_uncovered_event_reader: EventReader<TileUncoveredEvent>,
+        mut cascade_event_reader: EventReader<CascadeEvent>,
+        mut chord_event_reader: EventReader<ChordEvent>,
+        mut tile_mark_changed_event_reader: EventReader<TileMarkChangedEvent>,
+        mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+        mut board_completed_event_reader: EventReader<BoardCompletedEvent>,
+    ) {
+        let mut entries = Vec::new();
+
+        entries.extend(tile_uncovered_event_reader.iter().map(|e| {
+            GameLogEntry::TileUncovered {
+                board: e.board.0,
+                player: e.player.0,
+                coordinates: e.coordinates,
+                tile: e.tile,
+                cascade: e.cascade,
+            }
+        }));
+        entries.extend(cascade_event_reader.iter().map(|e| {
+            GameLogEntry::Cascade {
+                board: e.board.0,
+                player: e.player.0,
+                origin: e.origin,
+                tiles: e.tiles,
+            }
+        }));
+        entries.extend(chord_event_reader.iter().map(|e| {
+            GameLogEntry::Chord {
+                board: e.board.0,
+                player: e.player.0,
+                coordinates: e.coordinates,
+                tiles: e.tiles,
+            }
+        }));
+        entries.extend(tile_mark_changed_event_reader.iter().map(|e| {
+            GameLogEntry::MarkChanged {
+                board: e.board.0,
+                player: e.player.0,
+                coordinates: e.coordinates,
+                mark: e.mark,
+                bombs_left: e.bombs_left,
+            }
+        }));
+        entries.extend(bomb_explosion_event_reader.iter().map(|e| {
+            GameLogEntry::BombExplosion {
+                board: e.board.0,
+                player: e.player.0,
+                coordinates: e.coordinates,
+                elapsed_ms: e.elapsed.as_millis() as u64,
+            }
+        }));
+        entries.extend(board_completed_event_reader.iter().map(|e| {
+            GameLogEntry::BoardCompleted {
+                board: e.board.0,
+                player: e.player.0,
+                bombs: e.bombs,
+                elapsed_ms: e.elapsed.as_millis() as u64,
+            }
+        }));
+
+        if entries.is_empty() {
+            return;
+        }
+        let time_ms = time.elapsed().as_millis() as u64;
+        if let Err(e) = append_game_log(&options.path, time_ms, &entries) {
+            log::error!("Failed to write game log: {e}");
+        }
+    }
+}
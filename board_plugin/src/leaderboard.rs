@@ -0,0 +1,111 @@
+use crate::resources::{
+    GameOutcome, GameStats, Leaderboard, LeaderboardEntry, Replay,
+};
+use bevy::log;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Leaderboard storage and player name
+#[derive(Debug, Clone, Resource)]
+pub struct LeaderboardOptions {
+    /// Local leaderboard file
+    pub path: PathBuf,
+    /// Name stored along with new entries
+    pub name: String,
+}
+
+impl Default for LeaderboardOptions {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("leaderboard.json"),
+            name: "Player".to_string(),
+        }
+    }
+}
+
+/// Local high scores, single board wins are submitted with their replay
+pub struct LeaderboardPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for LeaderboardPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LeaderboardOptions>()
+            .add_systems(Startup, Self::load_leaderboard)
+            .add_systems(
+                Update,
+                Self::submit_score
+                    .run_if(in_state(self.running_state.clone()))
+                    .run_if(resource_exists::<Replay>()),
+            );
+
+        log::info!("Loaded Leaderboard Plugin");
+    }
+}
+
+impl<T> LeaderboardPlugin<T> {
+    fn load_leaderboard(
+        mut commands: Commands,
+        options: Res<LeaderboardOptions>,
+    ) {
+        match Leaderboard::load(&options.path) {
+            Ok(leaderboard) => commands.insert_resource(leaderboard),
+            Err(e) => {
+                log::error!("Failed to load leaderboard: {e}");
+                commands.insert_resource(Leaderboard::default());
+            }
+        }
+    }
+
+    /// Submit the replay once the board is won
+    fn submit_score(
+        mut commands: Commands,
+        game_stats: Res<GameStats>,
+        replay: Res<Replay>,
+        options: Res<LeaderboardOptions>,
+        mut leaderboard: ResMut<Leaderboard>,
+    ) {
+        let Some(outcome) = game_stats.outcome else {
+            return;
+        };
+        // A replay is only submitted once
+        commands.remove_resource::<Replay>();
+
+        let Some(last_action) = replay.actions.last() else {
+            return;
+        };
+        if outcome != GameOutcome::Won {
+            return;
+        }
+
+        let Some(difficulty) = replay.difficulty() else {
+            log::info!("Board can't be ranked, no high score");
+            return;
+        };
+        let entry = LeaderboardEntry {
+            name: options.name.clone(),
+            time_ms: last_action.time_ms,
+            replay: replay.clone(),
+        };
+
+        match leaderboard.submit(entry) {
+            Ok(Some(rank)) => {
+                log::info!("New high score on {difficulty}, rank {}", rank + 1);
+                if let Err(e) = leaderboard.save(&options.path) {
+                    log::error!("Failed to save leaderboard: {e}");
+                }
+            }
+            Ok(None) => log::info!("No high score on {difficulty}"),
+            Err(e) => log::warn!("Rejected leaderboard entry: {e}"),
+        }
+
+        for (i, entry) in leaderboard.entries(&difficulty).iter().enumerate() {
+            log::info!(
+                "{}. {} {:.3}s",
+                i + 1,
+                entry.name,
+                entry.time_ms as f32 / 1000.
+            );
+        }
+    }
+}
@@ -1,29 +1,130 @@
+mod animation;
+mod announcer;
+mod audio;
+mod bindings;
 mod bounds;
+mod commands;
 pub mod components;
-mod events;
+mod daily;
+mod end_screen;
+mod endless;
+pub mod events;
+mod game_log;
+mod leaderboard;
+pub mod net;
+mod pause;
+mod preferences;
 pub mod resources;
 mod systems;
+#[cfg(test)]
+mod tests;
 
+pub use animation::{AnimationOptions, AnimationPlugin};
+pub use announcer::{
+    Announcer, AnnouncerOptions, AnnouncerPlugin, BoardAnnouncer, LogAnnouncer,
+    RecordingAnnouncer, StdoutAnnouncer,
+};
+pub use audio::BoardAudioPlugin;
 use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
-use bevy::text::BreakLineOn;
 use bevy::window::PrimaryWindow;
-use bevy::{log, utils::HashMap};
+use bevy::{
+    log,
+    utils::{HashMap, HashSet},
+};
+pub use bindings::{ActionInput, BindingsOptions, BindingsPlugin};
 use bounds::Bounds2;
+pub use commands::{FlagTile, RestartBoard, RevealTile, SpawnBoard};
 use components::*;
+pub use daily::DailyChallengePlugin;
+pub use end_screen::{EndScreenPlugin, StatsOptions};
+pub use endless::EndlessBoardPlugin;
 use events::*;
+pub use game_log::{GameLogEntry, GameLogOptions, GameLogPlugin};
+pub use leaderboard::{LeaderboardOptions, LeaderboardPlugin};
+pub use pause::{PauseOptions, PausePlugin, PauseState};
+pub use preferences::{PreferencesOptions, PreferencesPlugin};
+use rand::{thread_rng, Rng};
 use resources::BoardAssets;
 pub use resources::BoardOptions;
 use resources::{
-    tile::Tile, tile_map::TileMap, Board, BoardPosition, TileSize,
+    tile::Tile, tile_map::TileMap, Board, BoardPlayers, BoardPosition,
+    GameMode, GameStats, InputMap, PlayerOptions, PlayerStats, Replay,
+    SkinTile, TileMask, TileMaskLoader, TileSize, Topology,
 };
 
+/// Sets of the board systems, for host apps to order their own systems
+///
+/// [`BoardSet::Input`] runs before the others, so events sent by player
+/// input are processed within the same frame, and [`BoardSet::Stats`] runs
+/// last. Every set only runs in the running state while not paused; host
+/// apps add their own conditions on top of these with
+/// `app.configure_set(Update, BoardSet::Input.run_if(...))`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, SystemSet)]
+pub enum BoardSet {
+    /// Built-in mouse, keyboard and gamepad handling
+    Input,
+    /// Reveals and chords, reading [`TileTriggerEvent`]s, and their outcome
+    Uncover,
+    /// Flags and question marks, reading [`TileMarkEvent`]s
+    Mark,
+    /// Replay and game stats recording
+    Stats,
+}
+
+/// Built-in input settings
+///
+/// Host apps driving the board with their own input, through the events or
+/// the [`RevealTile`] and [`FlagTile`] commands, can disable it.
+#[derive(Debug, Clone, Resource)]
+pub struct BoardInputOptions {
+    /// Systems of [`BoardSet::Input`] are skipped when disabled
+    pub enabled: bool,
+}
+
+impl Default for BoardInputOptions {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
 pub struct BoardPlugin<T> {
     pub running_state: T,
 }
 
 impl<T: States> Plugin for BoardPlugin<T> {
     fn build(&self, app: &mut App) {
+        // Shared with the endless board plugin when both are added
+        if !app.world.contains_resource::<State<PauseState>>() {
+            app.add_state::<PauseState>();
+        }
+        app.init_resource::<InputMap>()
+            .init_resource::<BoardInputOptions>()
+            .configure_sets(
+                Update,
+                (
+                    BoardSet::Input.before(BoardSet::Uncover),
+                    BoardSet::Input.before(BoardSet::Mark),
+                    BoardSet::Stats
+                        .after(BoardSet::Uncover)
+                        .after(BoardSet::Mark),
+                    BoardSet::Input.run_if(
+                        |options: Res<BoardInputOptions>| options.enabled,
+                    ),
+                ),
+            );
+        for set in [
+            BoardSet::Input,
+            BoardSet::Uncover,
+            BoardSet::Mark,
+            BoardSet::Stats,
+        ] {
+            app.configure_set(
+                Update,
+                set.run_if(in_state(self.running_state.clone()))
+                    .run_if(in_state(PauseState::Running)),
+            );
+        }
         app.add_systems(
             OnEnter(self.running_state.clone()),
             Self::create_board,
@@ -31,18 +132,58 @@ impl<T: States> Plugin for BoardPlugin<T> {
         .add_systems(
             Update,
             (
-                systems::input::handle_input,
-                systems::uncover::trigger_event_handler,
-                systems::uncover::uncover_tiles,
-                systems::mark::mark_tiles,
-            )
-                .run_if(in_state(self.running_state.clone())),
+                (
+                    systems::input::handle_input,
+                    systems::input::handle_keyboard_input,
+                    systems::input::update_cursors,
+                    systems::input::update_mouse_cursors,
+                    systems::input::preview_pressed_tiles
+                        .after(systems::input::handle_input),
+                )
+                    .in_set(BoardSet::Input),
+                (
+                    systems::uncover::trigger_event_handler,
+                    systems::uncover::uncover_tiles,
+                    systems::uncover::report_cascades
+                        .after(systems::uncover::uncover_tiles),
+                    systems::race::report_race_results
+                        .after(systems::uncover::uncover_tiles),
+                    systems::game_over::reveal_mines
+                        .after(systems::uncover::uncover_tiles),
+                    systems::ghost::sync_ghost_tiles,
+                )
+                    .in_set(BoardSet::Uncover),
+                systems::mark::mark_tiles.in_set(BoardSet::Mark),
+                (
+                    systems::stats::record_replay
+                        .before(systems::stats::update_game_stats),
+                    systems::stats::update_game_stats,
+                )
+                    .in_set(BoardSet::Stats),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                systems::theme::restyle_board_sprites
+                    .after(systems::game_over::reveal_mines),
+                systems::theme::sync_counter_glyphs
+                    .after(systems::theme::restyle_board_sprites),
+            ),
         )
         .add_systems(OnExit(self.running_state.clone()), Self::cleanup_board)
         .add_event::<TileTriggerEvent>()
         .add_event::<TileMarkEvent>()
         .add_event::<BombExplosionEvent>()
-        .add_event::<BoardCompletedEvent>();
+        .add_event::<BoardCompletedEvent>()
+        .add_event::<TileUncoveredEvent>()
+        .add_event::<TileMarkChangedEvent>()
+        .add_event::<ChordEvent>()
+        .add_event::<CascadeEvent>()
+        .init_resource::<PlayerStats>()
+        .init_resource::<GameStats>()
+        .add_asset::<TileMask>()
+        .init_asset_loader::<TileMaskLoader>();
 
         log::info!("Loaded Board Plugin");
 
@@ -52,6 +193,13 @@ impl<T: States> Plugin for BoardPlugin<T> {
             app.register_type::<BombNeighbor>();
             app.register_type::<Bomb>();
             app.register_type::<Uncover>();
+            app.register_type::<GhostTile>();
+            app.register_type::<BoardId>();
+            app.register_type::<BoardCursor>();
+            app.register_type::<MousePress>();
+            app.register_type::<PlayerId>();
+            app.register_type::<RevealOrigin>();
+            app.register_type::<BoardSprite>();
         }
     }
 }
@@ -61,31 +209,50 @@ impl<T> BoardPlugin<T> {
     pub fn create_board(
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
+        board_players: Option<Res<BoardPlayers>>,
         board_assets: Res<BoardAssets>,
+        masks: Res<Assets<TileMask>>,
         window_query: Query<&Window, With<PrimaryWindow>>,
     ) {
-        let options = board_options.map(|o| o.clone()).unwrap_or_default();
+        let mut options = board_options.map(|o| o.clone()).unwrap_or_default();
+        options.shape.resolve(&masks);
 
-        let mut tile_map =
-            TileMap::empty(options.map_size.0, options.map_size.1);
-        tile_map.set_bombs(options.bomb_count);
+        // Every layout is seeded so that games can be replayed
+        let seed = options.seed.unwrap_or_else(|| thread_rng().gen());
+        let tile_map = options.tile_map(seed);
 
         #[cfg(feature = "debug")]
         log::info!("{}", tile_map.console_output());
 
-        let Ok(window) = window_query.get_single() else {
-            log::error!("Window not found.");
-            return;
-        };
+        // Adaptive tiles get their minimum size without a window, so that
+        // headless apps can still create and restart boards
+        let available = window_query
+            .get_single()
+            .map_or(Vec2::ZERO, |w| Vec2::new(w.width(), w.height()));
 
-        let tile_size = match options.tile_size {
-            TileSize::Fixed(v) => v,
-            TileSize::Adaptive { min, max } => Self::adaptive_tile_size(
-                window,
-                (min, max),
-                (tile_map.width(), tile_map.height()),
-            ),
+        let board_players =
+            board_players.map(|p| p.clone()).unwrap_or_default();
+        let mut players: Vec<_> = board_players
+            .players
+            .into_iter()
+            .enumerate()
+            .map(|(i, player)| (PlayerId(i as u8), player))
+            .collect();
+        if players.is_empty() {
+            players.push((PlayerId(0), PlayerOptions::default()));
+        }
+
+        // One board per player in race, everyone on the same board in coop
+        let groups: Vec<_> = match board_players.mode {
+            GameMode::Race => players.into_iter().map(|p| vec![p]).collect(),
+            GameMode::Coop => vec![players],
         };
+
+        // Boards are laid out side by side, each in its own slot
+        let slot_size =
+            Vec2::new(available.x / groups.len() as f32, available.y);
+
+        let tile_size = Self::board_tile_size(&options, &tile_map, slot_size);
         log::debug!("tile size: {tile_size}");
 
         let board_size = Vec2::new(
@@ -94,14 +261,72 @@ impl<T> BoardPlugin<T> {
         );
         log::info!("board size: {board_size}");
 
-        let board_position = match options.position {
-            BoardPosition::Centered { offset } => {
-                Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.)
-                    + offset
-            }
-            BoardPosition::Custom(p) => p,
+        // Without a window, slots are spaced by the board size instead
+        let slot_width = if available.x > 0. {
+            slot_size.x
+        } else {
+            board_size.x + tile_size
         };
 
+        for (i, players) in groups.iter().enumerate() {
+            let slot_offset = Vec3::X
+                * ((i as f32 + 0.5 - groups.len() as f32 / 2.) * slot_width);
+
+            let board_position = match options.position {
+                BoardPosition::Centered { offset } => {
+                    Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.)
+                        + offset
+                        + slot_offset
+                }
+                BoardPosition::Custom(p) => {
+                    p + Vec3::X * i as f32 * (board_size.x + tile_size)
+                }
+            };
+
+            Self::spawn_board(
+                &mut commands,
+                BoardId(i as u8),
+                &options,
+                &tile_map,
+                tile_size,
+                board_position,
+                players,
+                &board_assets,
+            );
+        }
+
+        let player_count = groups.iter().map(Vec::len).sum();
+        let mut game_stats = GameStats::new(options.style(), player_count);
+        for i in 0..groups.len() {
+            game_stats.add_board(BoardId(i as u8), tile_map.bbbv());
+        }
+        commands.insert_resource(PlayerStats::default());
+        commands.insert_resource(game_stats);
+        if groups.len() == 1 {
+            commands.insert_resource(Replay::new(options, seed));
+        } else {
+            commands.remove_resource::<Replay>();
+        }
+    }
+
+    /// Spawn a board entity laid out from `tile_map`, played by `players`
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_board(
+        commands: &mut Commands,
+        id: BoardId,
+        options: &BoardOptions,
+        tile_map: &TileMap,
+        tile_size: f32,
+        board_position: Vec3,
+        players: &[(PlayerId, PlayerOptions)],
+        board_assets: &BoardAssets,
+    ) -> Entity {
+        let masked = tile_map.iter().flatten().any(Tile::is_void);
+        let board_size = Vec2::new(
+            tile_map.width() as f32 * tile_size,
+            tile_map.height() as f32 * tile_size,
+        );
+
         let mut covered_tiles = HashMap::with_capacity(
             (tile_map.width() * tile_map.height()).into(),
         );
@@ -110,59 +335,171 @@ impl<T> BoardPlugin<T> {
 
         let board_entity = commands
             .spawn_empty()
-            .insert(Name::new("Board"))
+            .insert(Name::new(format!("Board {}", id.0 + 1)))
             .insert(SpatialBundle {
                 visibility: Visibility::Visible,
                 transform: Transform::from_translation(board_position),
                 ..default()
             })
             .with_children(|parent| {
-                parent
-                    .spawn(SpriteBundle {
-                        sprite: Sprite {
-                            color: board_assets.board_material.color,
-                            custom_size: Some(board_size),
-                            ..default()
-                        },
-                        texture: board_assets.board_material.texture.clone(),
-                        transform: Transform::from_xyz(
-                            board_size.x / 2.,
-                            board_size.y / 2.,
-                            0.,
-                        ),
-                        ..default()
-                    })
-                    .insert(Name::new("Background"));
+                Self::spawn_background(
+                    parent,
+                    tile_map,
+                    tile_size,
+                    board_assets,
+                    masked,
+                );
 
                 Self::spawn_tiles(
                     parent,
-                    &tile_map,
+                    tile_map,
                     tile_size,
                     options.tile_padding,
-                    &board_assets,
+                    board_assets,
                     &mut covered_tiles,
                     &mut safe_start,
-                )
+                );
+
+                if options.topology == Topology::Toroidal {
+                    Self::spawn_ghost_tiles(
+                        parent,
+                        tile_map,
+                        tile_size,
+                        options.tile_padding,
+                        board_assets,
+                    );
+                }
+
+                for (id, player) in players {
+                    Self::spawn_cursor(
+                        parent,
+                        *id,
+                        player,
+                        tile_size,
+                        players.len() > 1,
+                    );
+                }
             })
             .id();
 
-        commands.insert_resource(Board {
-            tile_map,
-            bounds: Bounds2 {
-                position: board_position.xy(),
-                size: board_size,
+        commands.entity(board_entity).insert((
+            id,
+            options.clone(),
+            Board {
+                tile_map: tile_map.clone(),
+                bounds: Bounds2 {
+                    position: board_position.xy(),
+                    size: board_size,
+                },
+                tile_size,
+                covered_tiles,
+                marked_tiles: HashMap::new(),
+                question_tiles: HashSet::new(),
+                question_marks: options.question_marks,
+                no_flag: options.no_flag,
+                players: players.to_vec(),
             },
-            tile_size,
-            covered_tiles,
-            marked_tiles: Vec::new(),
-            entity: board_entity,
-        });
+        ));
 
         if options.safe_start {
             if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
+                commands.entity(entity).insert((Uncover, players[0].0));
             }
         }
+
+        board_entity
+    }
+
+    /// Spawn the cursor of a player
+    ///
+    /// Mouse cursors are only displayed when sharing a board with others.
+    fn spawn_cursor(
+        parent: &mut ChildBuilder,
+        id: PlayerId,
+        player: &PlayerOptions,
+        size: f32,
+        show_mouse_cursor: bool,
+    ) {
+        let mut cmd = parent.spawn((
+            Name::new(format!("Cursor {id}")),
+            BoardCursor,
+            id,
+            player.input,
+        ));
+
+        if player.input == BoardInput::Mouse && !show_mouse_cursor {
+            return;
+        }
+
+        cmd.insert(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(size)),
+                color: player.color,
+                ..default()
+            },
+            transform: Transform::from_xyz(size / 2., size / 2., 5.),
+            visibility: match player.input {
+                BoardInput::Mouse => Visibility::Hidden,
+                BoardInput::Keyboard(_) => Visibility::Visible,
+            },
+            ..default()
+        });
+    }
+
+    /// Spawn the board background, following the board shape when masked
+    fn spawn_background(
+        parent: &mut ChildBuilder,
+        tile_map: &TileMap,
+        size: f32,
+        board_assets: &BoardAssets,
+        masked: bool,
+    ) {
+        let board_size = Vec2::new(
+            tile_map.width() as f32 * size,
+            tile_map.height() as f32 * size,
+        );
+        let background_sprite = |custom_size, x, y| SpriteBundle {
+            sprite: Sprite {
+                color: board_assets.board_material.color,
+                custom_size: Some(custom_size),
+                ..default()
+            },
+            texture: board_assets.board_material.texture.clone(),
+            transform: Transform::from_xyz(x, y, 0.),
+            ..default()
+        };
+
+        if !masked {
+            parent
+                .spawn(background_sprite(
+                    board_size,
+                    board_size.x / 2.,
+                    board_size.y / 2.,
+                ))
+                .insert(Name::new("Background"))
+                .insert(BoardSprite::Background);
+            return;
+        }
+
+        parent
+            .spawn(SpatialBundle::default())
+            .insert(Name::new("Background"))
+            .with_children(|parent| {
+                for (y, line) in tile_map.iter().enumerate() {
+                    for (x, tile) in line.iter().enumerate() {
+                        if tile.is_void() {
+                            continue;
+                        }
+                        parent
+                            .spawn(background_sprite(
+                                Vec2::splat(size),
+                                (x as f32 * size) + (size / 2.),
+                                (y as f32 * size) + (size / 2.),
+                            ))
+                            .insert(BoardSprite::Background);
+                    }
+                }
+            });
     }
 
     fn spawn_tiles(
@@ -176,129 +513,261 @@ impl<T> BoardPlugin<T> {
     ) {
         for (y, line) in tile_map.iter().enumerate() {
             for (x, tile) in line.iter().enumerate() {
+                if tile.is_void() {
+                    continue;
+                }
                 let coordinates = Coordinates {
                     x: x as u16,
                     y: y as u16,
                 };
+                let transform = Transform::from_xyz(
+                    (x as f32 * size) + (size / 2.),
+                    (y as f32 * size) + (size / 2.),
+                    1.,
+                );
                 let mut cmd = parent.spawn_empty();
-                cmd.insert(SpriteBundle {
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::splat(size - padding)),
-                        color: board_assets.tile_material.color,
-                        ..default()
-                    },
-                    texture: board_assets.tile_material.texture.clone(),
-                    transform: Transform::from_xyz(
-                        (x as f32 * size) + (size / 2.),
-                        (y as f32 * size) + (size / 2.),
-                        1.,
-                    ),
-                    ..default()
-                })
-                .insert(Name::new(format!("Tile ({x}, {y})")))
-                .insert(coordinates)
-                .with_children(|parent| {
-                    let entity = parent
-                        .spawn(SpriteBundle {
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(size - padding)),
-                                color: board_assets.covered_tile_material.color,
-                                ..default()
-                            },
-                            texture: board_assets
-                                .covered_tile_material
-                                .texture
-                                .clone(),
-                            transform: Transform::from_xyz(0., 0., 2.),
+                match board_assets
+                    .skin_sprite(SkinTile::Uncovered, size - padding)
+                {
+                    Some(sprite) => cmd.insert(SpriteSheetBundle {
+                        transform,
+                        ..sprite
+                    }),
+                    None => cmd.insert(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(size - padding)),
+                            color: board_assets.tile_material.color,
                             ..default()
-                        })
-                        .insert(Name::new("Tile Cover"))
-                        .id();
-
-                    covered_tiles.insert(coordinates, entity);
-
-                    if safe_start_entity.is_none() && *tile == Tile::Empty {
-                        *safe_start_entity = Some(entity);
-                    }
-                });
-
-                match tile {
-                    Tile::Bomb => {
-                        cmd.insert(Bomb).with_children(|parent| {
-                            parent.spawn(SpriteBundle {
+                        },
+                        texture: board_assets.tile_material.texture.clone(),
+                        transform,
+                        ..default()
+                    }),
+                };
+                cmd.insert(Name::new(format!("Tile ({x}, {y})")))
+                    .insert(BoardSprite::Tile)
+                    .insert(coordinates)
+                    .with_children(|parent| {
+                        let transform = Transform::from_xyz(0., 0., 2.);
+                        let mut cover = match board_assets
+                            .skin_sprite(SkinTile::Covered, size - padding)
+                        {
+                            Some(sprite) => parent.spawn(SpriteSheetBundle {
+                                transform,
+                                ..sprite
+                            }),
+                            None => parent.spawn(SpriteBundle {
                                 sprite: Sprite {
                                     custom_size: Some(Vec2::splat(
                                         size - padding,
                                     )),
-                                    color: board_assets.bomb_material.color,
+                                    color: board_assets
+                                        .covered_tile_material
+                                        .color,
                                     ..default()
                                 },
-                                transform: Transform::from_xyz(0., 0., 1.),
                                 texture: board_assets
-                                    .bomb_material
+                                    .covered_tile_material
                                     .texture
                                     .clone(),
+                                transform,
                                 ..default()
-                            });
-                        });
+                            }),
+                        };
+                        let entity = cover
+                            .insert(Name::new("Tile Cover"))
+                            .insert(BoardSprite::Cover)
+                            .id();
+
+                        covered_tiles.insert(coordinates, entity);
+
+                        if safe_start_entity.is_none() && *tile == Tile::Empty {
+                            *safe_start_entity = Some(entity);
+                        }
+                    });
+
+                match tile {
+                    Tile::Bomb(v) => {
+                        cmd.insert(Bomb { count: *v }).with_children(
+                            |parent| {
+                                let transform = Transform::from_xyz(0., 0., 1.);
+                                let mut bomb = match board_assets
+                                    .skin_sprite(SkinTile::Mine, size - padding)
+                                {
+                                    Some(sprite) => {
+                                        parent.spawn(SpriteSheetBundle {
+                                            transform,
+                                            ..sprite
+                                        })
+                                    }
+                                    None => parent.spawn(SpriteBundle {
+                                        sprite: Sprite {
+                                            custom_size: Some(Vec2::splat(
+                                                size - padding,
+                                            )),
+                                            color: board_assets
+                                                .bomb_material
+                                                .color,
+                                            ..default()
+                                        },
+                                        transform,
+                                        texture: board_assets
+                                            .bomb_material
+                                            .texture
+                                            .clone(),
+                                        ..default()
+                                    }),
+                                };
+                                bomb.insert(BoardSprite::Bomb {
+                                    exploded: false,
+                                });
+
+                                if *v > 1 {
+                                    let mut text = board_assets
+                                        .bomb_count_text_bundle(
+                                            *v,
+                                            (size - padding) / 2.,
+                                        );
+                                    text.transform.translation.z = 2.;
+                                    parent
+                                        .spawn(text)
+                                        .insert(BoardSprite::Count(*v));
+                                }
+                            },
+                        );
                     }
                     Tile::BombNeighbor(v) => {
                         cmd.insert(BombNeighbor { count: *v }).with_children(
                             |parent| {
-                                parent.spawn(Self::bomb_count_text_bundle(
-                                    *v,
-                                    &board_assets,
-                                    size - padding,
-                                ));
+                                // Counters past 8 have no sprite in skins
+                                let mut counter = match board_assets
+                                    .skin_sprite(
+                                        SkinTile::Number(*v),
+                                        size - padding,
+                                    ) {
+                                    Some(mut sprite) => {
+                                        sprite.transform.translation.z = 1.;
+                                        parent.spawn(sprite)
+                                    }
+                                    None => parent.spawn(
+                                        board_assets.bomb_count_text_bundle(
+                                            *v,
+                                            size - padding,
+                                        ),
+                                    ),
+                                };
+                                counter.insert(BoardSprite::Counter(*v));
                             },
                         );
                     }
-                    Tile::Empty => (),
+                    Tile::Empty | Tile::Void => (),
                 };
             }
         }
     }
 
-    /// Compute a tile size that matches the window according to the tile map size
+    /// Spawn translucent copies of the opposite edges around a wrapping board
+    fn spawn_ghost_tiles(
+        parent: &mut ChildBuilder,
+        tile_map: &TileMap,
+        size: f32,
+        padding: f32,
+        board_assets: &BoardAssets,
+    ) {
+        let (width, height) = (tile_map.width(), tile_map.height());
+        let (w, h) = (width as i32, height as i32);
+
+        let columns = (0..height).flat_map(|y| {
+            [
+                ((-1, y as i32), Coordinates::new(width - 1, y)),
+                ((w, y as i32), Coordinates::new(0, y)),
+            ]
+        });
+        let rows = (0..width).flat_map(|x| {
+            [
+                ((x as i32, -1), Coordinates::new(x, height - 1)),
+                ((x as i32, h), Coordinates::new(x, 0)),
+            ]
+        });
+
+        let ghosts = columns
+            .chain(rows)
+            .filter(|(_, source)| !tile_map.is_void_at(*source));
+
+        for ((x, y), source) in ghosts {
+            parent
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(size - padding)),
+                        color: board_assets
+                            .covered_tile_material
+                            .color
+                            .with_a(systems::ghost::GHOST_ALPHA),
+                        ..default()
+                    },
+                    texture: board_assets.covered_tile_material.texture.clone(),
+                    transform: Transform::from_xyz(
+                        (x as f32 * size) + (size / 2.),
+                        (y as f32 * size) + (size / 2.),
+                        1.,
+                    ),
+                    ..default()
+                })
+                .insert(Name::new(format!("Ghost Tile {source}")))
+                .insert(GhostTile { source });
+        }
+    }
+
+    /// Tile size of a board laid out in `available` space
+    fn board_tile_size(
+        options: &BoardOptions,
+        tile_map: &TileMap,
+        available: Vec2,
+    ) -> f32 {
+        // Keep room for a ghost row/column on each side of wrapping boards
+        let margin = match options.topology {
+            Topology::Bounded => 0,
+            Topology::Toroidal => 2,
+        };
+
+        match options.tile_size {
+            TileSize::Fixed(v) => v,
+            TileSize::Adaptive { min, max } => Self::adaptive_tile_size(
+                available,
+                (min, max),
+                (tile_map.width() + margin, tile_map.height() + margin),
+            ),
+        }
+    }
+
+    /// Compute a tile size that fits the available space and the tile map size
     fn adaptive_tile_size(
-        window: &Window,
+        available: Vec2,
         (min, max): (f32, f32),
         (width, height): (u16, u16),
     ) -> f32 {
-        let max_width = window.width() / width as f32;
-        let max_height = window.height() / height as f32;
+        let max_width = available.x / width as f32;
+        let max_height = available.y / height as f32;
 
         max_width.min(max_height).clamp(min, max)
     }
 
-    /// Generate bomb counter text 2D Bundle for a given value
-    fn bomb_count_text_bundle(
-        count: u8,
-        board_assets: &BoardAssets,
-        size: f32,
-    ) -> Text2dBundle {
-        let color = board_assets.get_bomb_color(count);
-
-        Text2dBundle {
-            text: Text {
-                sections: vec![TextSection {
-                    value: count.to_string(),
-                    style: TextStyle {
-                        color,
-                        font: board_assets.bomb_counter_font.clone(),
-                        font_size: size,
-                    },
-                }],
-                alignment: TextAlignment::Center,
-                linebreak_behavior: BreakLineOn::WordBoundary,
-            },
-            transform: Transform::from_xyz(0., 0., 1.),
-            ..default()
+    fn cleanup_board(
+        boards: Query<Entity, With<Board>>,
+        player_stats: Res<PlayerStats>,
+        mut commands: Commands,
+    ) {
+        for (player, stats) in player_stats.iter() {
+            log::info!(
+                "{player}: {} tiles uncovered, {} flags placed, {} bombs hit",
+                stats.tiles_uncovered,
+                stats.flags_placed,
+                stats.bombs_uncovered
+            );
         }
-    }
 
-    fn cleanup_board(board: Res<Board>, mut commands: Commands) {
-        commands.entity(board.entity).despawn_recursive();
-        commands.remove_resource::<Board>();
+        for entity in boards.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
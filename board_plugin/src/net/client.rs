@@ -0,0 +1,494 @@
+use crate::bounds::Bounds2;
+use crate::components::{Coordinates, PlayerId};
+use crate::net::protocol::{
+    decode_message, write_message, ClientMessage, GameMode, ServerMessage,
+    DEFAULT_PORT, PROTOCOL_VERSION,
+};
+use crate::resources::{
+    player_color, tile::Tile, BoardAssets, InputAction, InputMap, PlayerStats,
+    RevealedTile,
+};
+use crate::ActionInput;
+use bevy::log;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+use std::io::{self, ErrorKind, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Network client options
+#[derive(Debug, Clone, Resource)]
+pub struct NetClientOptions {
+    /// Game server address
+    pub address: String,
+    /// Player name sent to the server
+    pub name: String,
+    /// Padding between tiles
+    pub tile_padding: f32,
+}
+
+impl Default for NetClientOptions {
+    fn default() -> Self {
+        Self {
+            address: format!("127.0.0.1:{DEFAULT_PORT}"),
+            name: "Player".to_string(),
+            tile_padding: 1.,
+        }
+    }
+}
+
+/// Connection to the game server
+#[derive(Debug, Resource)]
+pub struct NetConnection {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    /// Player id assigned by the server
+    pub player: Option<u8>,
+}
+
+impl NetConnection {
+    /// Connect to `address` and send the join message
+    pub fn connect(address: &str, name: &str) -> io::Result<Self> {
+        let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(ErrorKind::InvalidInput, "invalid server address")
+        })?;
+        let mut stream =
+            TcpStream::connect_timeout(&address, Duration::from_secs(5))?;
+
+        write_message(
+            &mut stream,
+            &ClientMessage::Join {
+                version: PROTOCOL_VERSION,
+                name: name.to_string(),
+            },
+        )?;
+        stream.set_nonblocking(true)?;
+
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+            player: None,
+        })
+    }
+
+    /// Send a message to the server
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        write_message(&mut self.stream, message)
+    }
+
+    /// Retrieve the messages received since the last poll, without blocking
+    pub fn poll(&mut self) -> io::Result<Vec<ServerMessage>> {
+        let mut chunk = [0; 4096];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        ErrorKind::ConnectionAborted,
+                        "server closed the connection",
+                    ))
+                }
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut messages = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            messages.push(decode_message(&String::from_utf8_lossy(&line))?);
+        }
+
+        Ok(messages)
+    }
+}
+
+/// Client side board, only knows about the tiles the server revealed
+#[derive(Debug, Resource)]
+pub struct NetBoard {
+    pub mode: GameMode,
+    pub bounds: Bounds2,
+    pub tile_size: f32,
+    pub entity: Entity,
+    pub tiles: HashMap<Coordinates, Entity>,
+    pub covered_tiles: HashMap<Coordinates, Entity>,
+}
+
+/// Networked game client, the board is driven by a remote game server
+pub struct NetClientPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for NetClientPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(self.running_state.clone()), connect)
+            .add_systems(
+                Update,
+                (receive_messages, handle_input)
+                    .chain()
+                    .run_if(in_state(self.running_state.clone()))
+                    .run_if(resource_exists::<NetConnection>()),
+            )
+            .add_systems(OnExit(self.running_state.clone()), disconnect)
+            .init_resource::<InputMap>()
+            .init_resource::<PlayerStats>();
+
+        log::info!("Loaded Net Client Plugin");
+    }
+}
+
+fn connect(mut commands: Commands, options: Option<Res<NetClientOptions>>) {
+    let options = options.map(|o| o.clone()).unwrap_or_default();
+
+    match NetConnection::connect(&options.address, &options.name) {
+        Ok(connection) => {
+            log::info!("Connected to {}", options.address);
+            commands.insert_resource(connection);
+            commands.insert_resource(PlayerStats::default());
+        }
+        Err(e) => log::error!("Failed to connect to {}: {e}", options.address),
+    }
+}
+
+fn disconnect(mut commands: Commands, board: Option<Res<NetBoard>>) {
+    if let Some(board) = board {
+        commands.entity(board.entity).despawn_recursive();
+    }
+    commands.remove_resource::<NetBoard>();
+    commands.remove_resource::<NetConnection>();
+}
+
+fn receive_messages(
+    mut commands: Commands,
+    mut connection: ResMut<NetConnection>,
+    mut board: Option<ResMut<NetBoard>>,
+    mut player_stats: ResMut<PlayerStats>,
+    options: Option<Res<NetClientOptions>>,
+    board_assets: Res<BoardAssets>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+) {
+    let messages = match connection.poll() {
+        Ok(messages) => messages,
+        Err(e) => {
+            log::error!("Connection lost: {e}");
+            commands.remove_resource::<NetConnection>();
+            return;
+        }
+    };
+
+    // The board spawned by this batch, the resource is only inserted later
+    let mut spawned: Option<NetBoard> = None;
+
+    for message in messages {
+        log::trace!("Received {message:?}");
+        let me = connection.player;
+
+        match message {
+            ServerMessage::Welcome { player, .. } => {
+                log::info!("Joined as player {player}");
+                connection.player = Some(player);
+            }
+            ServerMessage::Rejected { reason } => {
+                log::error!("Rejected by the server: {reason}");
+            }
+            ServerMessage::BoardSpec {
+                width,
+                height,
+                mode,
+                ..
+            } => {
+                let Ok(window) = window_query.get_single() else {
+                    log::error!("Window not found.");
+                    continue;
+                };
+                let padding = options.as_ref().map_or(1., |o| o.tile_padding);
+                spawned = Some(spawn_board(
+                    &mut commands,
+                    (width, height),
+                    mode,
+                    window,
+                    padding,
+                    &board_assets,
+                ));
+            }
+            ServerMessage::Revealed { player, tiles } => {
+                let Some(board) = spawned.as_mut().or(board.as_deref_mut())
+                else {
+                    continue;
+                };
+                if board.mode == GameMode::Coop || Some(player) == me {
+                    player_stats
+                        .player_mut(PlayerId(player))
+                        .tiles_uncovered += tiles.len() as u32;
+                    for tile in tiles {
+                        reveal_tile(&mut commands, board, &board_assets, tile);
+                    }
+                }
+            }
+            ServerMessage::Exploded { player, tile } => {
+                log::info!("Player {player} exploded on {}", tile.coordinates);
+                let Some(board) = spawned.as_mut().or(board.as_deref_mut())
+                else {
+                    continue;
+                };
+                if board.mode == GameMode::Coop || Some(player) == me {
+                    player_stats
+                        .player_mut(PlayerId(player))
+                        .bombs_uncovered += 1;
+                    reveal_tile(&mut commands, board, &board_assets, tile);
+                }
+            }
+            ServerMessage::Marked {
+                player,
+                coordinates,
+                flags,
+            } => {
+                let Some(board) = spawned.as_ref().or(board.as_deref()) else {
+                    continue;
+                };
+                let Some(cover) = board.covered_tiles.get(&coordinates) else {
+                    continue;
+                };
+
+                commands.entity(*cover).despawn_descendants();
+                if flags == 0 {
+                    continue;
+                }
+                player_stats.player_mut(PlayerId(player)).flags_placed += 1;
+
+                // Flags are tinted with the color of their owner in coop
+                let color = match board.mode {
+                    GameMode::Coop => player_color(PlayerId(player)).with_a(1.),
+                    GameMode::Race => board_assets.flag_material.color,
+                };
+                let size = board.tile_size;
+
+                commands.entity(*cover).with_children(|parent| {
+                    parent
+                        .spawn(SpriteBundle {
+                            texture: board_assets.flag_material.texture.clone(),
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::splat(size)),
+                                color,
+                                ..default()
+                            },
+                            transform: Transform::from_xyz(0., 0., 1.),
+                            ..default()
+                        })
+                        .insert(Name::new("Flag"));
+                });
+            }
+            ServerMessage::Progress { player, remaining } => {
+                log::info!("Player {player} has {remaining} tiles left");
+            }
+            ServerMessage::Eliminated { player, remaining } => {
+                log::info!(
+                    "Player {player} is out with {remaining} tiles left"
+                );
+            }
+            ServerMessage::Invalid { reason } => {
+                log::debug!("Invalid action: {reason}");
+            }
+            ServerMessage::Finished { winner, cleared } => {
+                match winner {
+                    Some(winner) if Some(winner) == me => {
+                        log::info!("You won!")
+                    }
+                    Some(winner) => log::info!("Player {winner} won"),
+                    None if cleared => log::info!("Board cleared!"),
+                    None => log::info!("Game over"),
+                }
+                for (player, stats) in player_stats.iter() {
+                    log::info!(
+                        "{player}: {} tiles uncovered, {} flags placed",
+                        stats.tiles_uncovered,
+                        stats.flags_placed
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(spawned) = spawned {
+        commands.insert_resource(spawned);
+    }
+}
+
+/// Send the actions of the player on the tile under the mouse, revealed
+/// numbers are chorded by the server
+fn handle_input(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut connection: ResMut<NetConnection>,
+    board: Option<Res<NetBoard>>,
+    actions: ActionInput,
+) {
+    let Some(board) = board else {
+        return;
+    };
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Some(coordinates) =
+        board.bounds.cursor_coordinates(window, board.tile_size)
+    else {
+        return;
+    };
+
+    let mut messages = Vec::new();
+    if actions.just_pressed(InputAction::Reveal)
+        || actions.just_pressed(InputAction::Chord)
+    {
+        messages.push(ClientMessage::Reveal { coordinates });
+    }
+    if actions.just_pressed(InputAction::Flag) {
+        messages.push(ClientMessage::Mark { coordinates });
+    }
+
+    for message in messages {
+        if let Err(e) = connection.send(&message) {
+            log::error!("Failed to send {message:?}: {e}");
+        }
+    }
+}
+
+/// Spawn a fully covered board
+fn spawn_board(
+    commands: &mut Commands,
+    (width, height): (u16, u16),
+    mode: GameMode,
+    window: &Window,
+    padding: f32,
+    board_assets: &BoardAssets,
+) -> NetBoard {
+    let tile_size = (window.width() / width as f32)
+        .min(window.height() / height as f32)
+        .clamp(10., 50.);
+    let board_size = Vec2::new(width as f32, height as f32) * tile_size;
+    let board_position = (-board_size / 2.).extend(0.);
+
+    let mut tiles = HashMap::new();
+    let mut covered_tiles = HashMap::new();
+
+    let entity = commands
+        .spawn(SpatialBundle::from_transform(Transform::from_translation(
+            board_position,
+        )))
+        .insert(Name::new("Board"))
+        .with_children(|parent| {
+            parent
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: board_assets.board_material.color,
+                        custom_size: Some(board_size),
+                        ..default()
+                    },
+                    texture: board_assets.board_material.texture.clone(),
+                    transform: Transform::from_xyz(
+                        board_size.x / 2.,
+                        board_size.y / 2.,
+                        0.,
+                    ),
+                    ..default()
+                })
+                .insert(Name::new("Background"));
+
+            for y in 0..height {
+                for x in 0..width {
+                    let coordinates = Coordinates { x, y };
+                    let mut cmd = parent.spawn(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(tile_size - padding)),
+                            color: board_assets.tile_material.color,
+                            ..default()
+                        },
+                        texture: board_assets.tile_material.texture.clone(),
+                        transform: Transform::from_xyz(
+                            (x as f32 * tile_size) + (tile_size / 2.),
+                            (y as f32 * tile_size) + (tile_size / 2.),
+                            1.,
+                        ),
+                        ..default()
+                    });
+                    cmd.insert(Name::new(format!("Tile ({x}, {y})")))
+                        .insert(coordinates);
+
+                    cmd.with_children(|parent| {
+                        let cover = parent
+                            .spawn(SpriteBundle {
+                                sprite: Sprite {
+                                    custom_size: Some(Vec2::splat(
+                                        tile_size - padding,
+                                    )),
+                                    color: board_assets
+                                        .covered_tile_material
+                                        .color,
+                                    ..default()
+                                },
+                                texture: board_assets
+                                    .covered_tile_material
+                                    .texture
+                                    .clone(),
+                                transform: Transform::from_xyz(0., 0., 2.),
+                                ..default()
+                            })
+                            .insert(Name::new("Tile Cover"))
+                            .id();
+                        covered_tiles.insert(coordinates, cover);
+                    });
+                    tiles.insert(coordinates, cmd.id());
+                }
+            }
+        })
+        .id();
+
+    NetBoard {
+        mode,
+        bounds: Bounds2 {
+            position: board_position.xy(),
+            size: board_size,
+        },
+        tile_size,
+        entity,
+        tiles,
+        covered_tiles,
+    }
+}
+
+/// Remove the cover of a tile revealed by the server and show its content
+fn reveal_tile(
+    commands: &mut Commands,
+    board: &mut NetBoard,
+    board_assets: &BoardAssets,
+    RevealedTile { coordinates, tile }: RevealedTile,
+) {
+    let Some(cover) = board.covered_tiles.remove(&coordinates) else {
+        return;
+    };
+    commands.entity(cover).despawn_recursive();
+
+    let Some(entity) = board.tiles.get(&coordinates) else {
+        return;
+    };
+    let size = board.tile_size;
+
+    commands.entity(*entity).with_children(|parent| match tile {
+        Tile::Bomb(_) => {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(size)),
+                    color: board_assets.bomb_material.color,
+                    ..default()
+                },
+                texture: board_assets.bomb_material.texture.clone(),
+                transform: Transform::from_xyz(0., 0., 1.),
+                ..default()
+            });
+        }
+        Tile::BombNeighbor(v) => {
+            parent.spawn(board_assets.bomb_count_text_bundle(v, size));
+        }
+        Tile::Empty | Tile::Void => (),
+    });
+}
@@ -0,0 +1,5 @@
+pub use client::{NetClientOptions, NetClientPlugin};
+
+mod client;
+pub mod protocol;
+pub mod server;
@@ -0,0 +1,117 @@
+//! Versioned client/server message protocol
+//!
+//! Messages are JSON objects, one per line. The server never sends the seed
+//! nor any covered tile content, clients only learn about revealed tiles.
+
+use crate::components::Coordinates;
+pub use crate::resources::GameMode;
+use crate::resources::RevealedTile;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Read, Write};
+
+/// Current protocol version, clients with a different version are rejected
+pub const PROTOCOL_VERSION: u16 = 2;
+
+/// Default server port
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Maximum length of a message line, in bytes
+pub const MAX_LINE: u64 = 4096;
+
+/// Message sent by a client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ClientMessage {
+    /// First message of a client
+    Join { version: u16, name: String },
+    /// Uncover a tile
+    Reveal { coordinates: Coordinates },
+    /// Cycle the flags of a tile
+    Mark { coordinates: Coordinates },
+}
+
+/// Message sent by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ServerMessage {
+    /// The client joined as `player`
+    Welcome { version: u16, player: u8 },
+    /// The client was refused, the connection is closed
+    Rejected { reason: String },
+    /// The game started, board layout without its content
+    BoardSpec {
+        width: u16,
+        height: u16,
+        bomb_count: u16,
+        mode: GameMode,
+        players: u8,
+    },
+    /// Tiles uncovered on the board of `player`
+    Revealed {
+        player: u8,
+        tiles: Vec<RevealedTile>,
+    },
+    /// Flags of a tile changed on the board of `player`
+    Marked {
+        player: u8,
+        coordinates: Coordinates,
+        flags: u8,
+    },
+    /// `player` uncovered a bomb
+    Exploded { player: u8, tile: RevealedTile },
+    /// `player` uncovered a bomb, sent to opponents in race without the
+    /// tile since every board shares the same layout
+    Eliminated { player: u8, remaining: u16 },
+    /// Remaining covered safe tiles of `player`, sent to opponents in race
+    Progress { player: u8, remaining: u16 },
+    /// The action of the client was refused
+    Invalid { reason: String },
+    /// The game is over
+    Finished { winner: Option<u8>, cleared: bool },
+}
+
+/// Write a single message as a JSON line
+pub fn write_message<W: Write, M: Serialize>(
+    writer: &mut W,
+    message: &M,
+) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+/// Read a single JSON line message, `None` once the stream is closed
+pub fn read_message<R: BufRead, M: for<'de> Deserialize<'de>>(
+    reader: &mut R,
+) -> io::Result<Option<M>> {
+    match read_line(reader)? {
+        Some(line) => decode_message(&line).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Read a single line of at most [`MAX_LINE`] bytes, `None` once the stream
+/// is closed
+///
+/// Longer lines fail, the rest of the line is left unread.
+pub fn read_line<R: BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if reader.by_ref().take(MAX_LINE).read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') && line.len() as u64 >= MAX_LINE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message longer than {MAX_LINE} bytes"),
+        ));
+    }
+    Ok(Some(line))
+}
+
+/// Decode a single JSON line message
+pub fn decode_message<M: for<'de> Deserialize<'de>>(
+    line: &str,
+) -> io::Result<M> {
+    serde_json::from_str(line)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,490 @@
+//! Authoritative game server
+//!
+//! The server owns the tile map and validates every action, clients only
+//! receive the content of the tiles they uncover.
+
+use crate::net::protocol::{
+    decode_message, read_line, write_message, ClientMessage, GameMode,
+    ServerMessage, DEFAULT_PORT, PROTOCOL_VERSION,
+};
+use crate::resources::{tile_map::TileMap, BoardState, RevealOutcome};
+use bevy::log;
+use std::io::{self, BufReader};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Game server options
+#[derive(Debug, Clone)]
+pub struct ServerOptions {
+    /// Listening address
+    pub address: String,
+    /// Number of players, the game starts once they all joined
+    pub players: u8,
+    pub mode: GameMode,
+    /// Tile map size (width, height)
+    pub map_size: (u16, u16),
+    /// Number of bombs spawned
+    pub bomb_count: u16,
+    /// Seed of the shared layout, never sent to clients
+    pub seed: u64,
+    /// Uncover a safe tile for every player when the game starts
+    pub safe_start: bool,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self {
+            address: format!("127.0.0.1:{DEFAULT_PORT}"),
+            players: 2,
+            mode: GameMode::default(),
+            map_size: (20, 20),
+            bomb_count: 50,
+            seed: 0,
+            safe_start: true,
+        }
+    }
+}
+
+/// Players receiving a server message
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Recipient {
+    All,
+    Player(u8),
+    /// Everyone but the given player
+    Others(u8),
+}
+
+/// Server message and its recipients
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outgoing {
+    pub recipient: Recipient,
+    pub message: ServerMessage,
+}
+
+impl Outgoing {
+    fn new(recipient: Recipient, message: ServerMessage) -> Self {
+        Self { recipient, message }
+    }
+}
+
+/// Authoritative game state, independent of the transport
+#[derive(Debug)]
+pub struct GameServer {
+    options: ServerOptions,
+    /// One board per player in race, a single shared board in coop
+    boards: Vec<BoardState>,
+    /// Names of the players by id, free slots are `None` until the start
+    names: Vec<Option<String>>,
+    started: bool,
+    finished: bool,
+}
+
+impl GameServer {
+    pub fn new(options: ServerOptions) -> Self {
+        let mut tile_map =
+            TileMap::empty(options.map_size.0, options.map_size.1);
+        tile_map.set_bombs_seeded(options.bomb_count, options.seed);
+
+        let board_count = match options.mode {
+            GameMode::Race => options.players.max(1) as usize,
+            GameMode::Coop => 1,
+        };
+        let boards = vec![BoardState::new(tile_map); board_count];
+        let players = options.players as usize;
+
+        Self {
+            options,
+            boards,
+            names: vec![None; players],
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Register a new player, returning its id
+    pub fn join(&mut self, version: u16, name: String) -> Result<u8, String> {
+        if version != PROTOCOL_VERSION {
+            return Err(format!(
+                "unsupported protocol version {version}, expected {PROTOCOL_VERSION}"
+            ));
+        }
+        let slot = self.names.iter().position(Option::is_none);
+        let Some(player) = slot.filter(|_| !self.started) else {
+            return Err("game is full".to_string());
+        };
+
+        log::info!("{name} joined");
+        self.names[player] = Some(name);
+
+        Ok(player as u8)
+    }
+
+    /// Free the slot of `player` if the game didn't start yet, so that
+    /// another client can take it
+    pub fn leave(&mut self, player: u8) {
+        if self.started {
+            return;
+        }
+        let slot = self.names.get_mut(player as usize);
+        if let Some(name) = slot.and_then(Option::take) {
+            log::info!("{name} left before the start");
+        }
+    }
+
+    /// Are all players connected
+    pub fn is_ready(&self) -> bool {
+        !self.started && self.names.iter().all(Option::is_some)
+    }
+
+    /// Did the game start
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Start the game, sending the board layout and the safe start tiles
+    pub fn start(&mut self) -> Vec<Outgoing> {
+        self.started = true;
+
+        let tile_map = self.boards[0].tile_map();
+        let mut outgoing = vec![Outgoing::new(
+            Recipient::All,
+            ServerMessage::BoardSpec {
+                width: tile_map.width(),
+                height: tile_map.height(),
+                bomb_count: tile_map.bomb_count(),
+                mode: self.options.mode,
+                players: self.options.players,
+            },
+        )];
+
+        if !self.options.safe_start {
+            return outgoing;
+        }
+
+        let safe_start = tile_map.safe_start();
+
+        if let Some(coords) = safe_start {
+            for player in 0..self.boards.len() as u8 {
+                if let RevealOutcome::Revealed(tiles) =
+                    self.boards[player as usize].reveal(coords)
+                {
+                    let recipient = match self.options.mode {
+                        GameMode::Race => Recipient::Player(player),
+                        GameMode::Coop => Recipient::All,
+                    };
+                    outgoing.push(Outgoing::new(
+                        recipient,
+                        ServerMessage::Revealed { player, tiles },
+                    ));
+                }
+            }
+        }
+
+        outgoing
+    }
+
+    /// Validate and apply a player action
+    pub fn handle(
+        &mut self,
+        player: u8,
+        message: ClientMessage,
+    ) -> Vec<Outgoing> {
+        let invalid = |reason: &str| {
+            vec![Outgoing::new(
+                Recipient::Player(player),
+                ServerMessage::Invalid {
+                    reason: reason.to_string(),
+                },
+            )]
+        };
+
+        if !self.started {
+            return invalid("game not started");
+        }
+        if self.finished {
+            return invalid("game is over");
+        }
+
+        let mode = self.options.mode;
+        let board_index = match mode {
+            GameMode::Race => player as usize,
+            GameMode::Coop => 0,
+        };
+        let Some(board) = self.boards.get_mut(board_index) else {
+            return invalid("unknown player");
+        };
+        let own_board = match mode {
+            GameMode::Race => Recipient::Player(player),
+            GameMode::Coop => Recipient::All,
+        };
+
+        match message {
+            ClientMessage::Join { .. } => invalid("already joined"),
+            ClientMessage::Mark { coordinates } => {
+                match board.toggle_mark(coordinates) {
+                    Some(mark) => vec![Outgoing::new(
+                        own_board,
+                        ServerMessage::Marked {
+                            player,
+                            coordinates,
+                            flags: mark.flags(),
+                        },
+                    )],
+                    None => invalid("tile can't be marked"),
+                }
+            }
+            ClientMessage::Reveal { coordinates } => {
+                match board.reveal(coordinates) {
+                    RevealOutcome::Invalid => {
+                        invalid("tile can't be uncovered")
+                    }
+                    RevealOutcome::Revealed(tiles) => {
+                        let remaining = board.remaining_safe_tiles();
+                        let completed = board.is_completed();
+
+                        let mut outgoing = vec![Outgoing::new(
+                            own_board,
+                            ServerMessage::Revealed { player, tiles },
+                        )];
+                        if mode == GameMode::Race {
+                            outgoing.push(Outgoing::new(
+                                Recipient::Others(player),
+                                ServerMessage::Progress { player, remaining },
+                            ));
+                        }
+                        if completed {
+                            self.finished = true;
+                            let winner = match mode {
+                                GameMode::Race => Some(player),
+                                GameMode::Coop => None,
+                            };
+                            outgoing.push(Outgoing::new(
+                                Recipient::All,
+                                ServerMessage::Finished {
+                                    winner,
+                                    cleared: true,
+                                },
+                            ));
+                        }
+                        outgoing
+                    }
+                    RevealOutcome::Exploded { revealed, tile } => {
+                        let remaining = board.remaining_safe_tiles();
+
+                        let mut outgoing = Vec::new();
+                        if !revealed.is_empty() {
+                            outgoing.push(Outgoing::new(
+                                own_board,
+                                ServerMessage::Revealed {
+                                    player,
+                                    tiles: revealed,
+                                },
+                            ));
+                        }
+                        outgoing.push(Outgoing::new(
+                            own_board,
+                            ServerMessage::Exploded { player, tile },
+                        ));
+                        // Race boards share their layout, so opponents are
+                        // told the player is out without learning the mine
+                        if mode == GameMode::Race {
+                            outgoing.push(Outgoing::new(
+                                Recipient::Others(player),
+                                ServerMessage::Eliminated { player, remaining },
+                            ));
+                        }
+                        // In race the game goes on until everyone exploded
+                        if self.boards.iter().all(BoardState::is_exploded) {
+                            self.finished = true;
+                            outgoing.push(Outgoing::new(
+                                Recipient::All,
+                                ServerMessage::Finished {
+                                    winner: None,
+                                    cleared: false,
+                                },
+                            ));
+                        }
+                        outgoing
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Outgoing message queues of the connected clients, indexed by player id
+///
+/// Each client is written to by its own thread, so that a slow client never
+/// holds the server state.
+type Clients = Vec<Option<Sender<ServerMessage>>>;
+
+/// Delay between polls of the listener while waiting for players
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Listen for clients and run a single game
+pub fn run(options: ServerOptions) -> io::Result<()> {
+    let listener = TcpListener::bind(&options.address)?;
+    log::info!("Listening on {}", listener.local_addr()?);
+
+    serve(listener, options)
+}
+
+/// Run a single game on an already bound listener
+pub fn serve(listener: TcpListener, options: ServerOptions) -> io::Result<()> {
+    let players = options.players as usize;
+    let state = Arc::new(Mutex::new((
+        GameServer::new(options),
+        Clients::from_iter((0..players).map(|_| None)),
+    )));
+
+    // Connections are accepted until the game starts, failed joins and
+    // players leaving early free their slot for the next ones
+    listener.set_nonblocking(true)?;
+    let mut handles = Vec::new();
+    while !state.lock().expect("server state poisoned").0.is_started() {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_INTERVAL);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        stream.set_nonblocking(false)?;
+        let state = state.clone();
+        handles.push(thread::spawn(move || {
+            if let Err(e) = handle_client(stream, &state) {
+                log::warn!("Client error: {e}");
+            }
+        }));
+    }
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    Ok(())
+}
+
+fn handle_client(
+    stream: TcpStream,
+    state: &Mutex<(GameServer, Clients)>,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let join = read_line(&mut reader)?
+        .and_then(|line| decode_message::<ClientMessage>(&line).ok());
+    let Some(ClientMessage::Join { version, name }) = join else {
+        write_message(
+            &mut writer,
+            &ServerMessage::Rejected {
+                reason: "expected a join message".to_string(),
+            },
+        )?;
+        return Ok(());
+    };
+
+    let joined = {
+        let mut guard = state.lock().expect("server state poisoned");
+        let (server, clients) = &mut *guard;
+
+        match server.join(version, name) {
+            Ok(player) => {
+                let client = spawn_writer(player, writer.try_clone()?);
+                let _ = client.send(ServerMessage::Welcome {
+                    version: PROTOCOL_VERSION,
+                    player,
+                });
+                clients[player as usize] = Some(client);
+
+                if server.is_ready() {
+                    let outgoing = server.start();
+                    dispatch(clients, outgoing);
+                }
+
+                Ok(player)
+            }
+            Err(reason) => Err(reason),
+        }
+    };
+    let player = match joined {
+        Ok(player) => player,
+        Err(reason) => {
+            write_message(&mut writer, &ServerMessage::Rejected { reason })?;
+            return Ok(());
+        }
+    };
+
+    let result = loop {
+        let line = match read_line(&mut reader) {
+            Ok(Some(line)) => line,
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(e),
+        };
+
+        let mut guard = state.lock().expect("server state poisoned");
+        let (server, clients) = &mut *guard;
+
+        // Malformed messages are refused without dropping the client
+        let outgoing = match decode_message(&line) {
+            Ok(message) => server.handle(player, message),
+            Err(e) => vec![Outgoing::new(
+                Recipient::Player(player),
+                ServerMessage::Invalid {
+                    reason: format!("malformed message: {e}"),
+                },
+            )],
+        };
+        dispatch(clients, outgoing);
+    };
+
+    // Dropping the queue ends the writer thread, closing the connection
+    log::info!("Player {player} disconnected");
+    let mut guard = state.lock().expect("server state poisoned");
+    let (server, clients) = &mut *guard;
+    server.leave(player);
+    clients[player as usize] = None;
+
+    result
+}
+
+/// Spawn the thread writing the queued messages of `player` to `stream`,
+/// returning the queue
+fn spawn_writer(player: u8, mut stream: TcpStream) -> Sender<ServerMessage> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for message in receiver {
+            if let Err(e) = write_message(&mut stream, &message) {
+                log::warn!("Failed to reach player {player}: {e}");
+                break;
+            }
+        }
+    });
+    sender
+}
+
+/// Queue messages for their recipients, dropping clients that can't be
+/// reached
+fn dispatch(clients: &mut Clients, outgoing: Vec<Outgoing>) {
+    for Outgoing { recipient, message } in outgoing {
+        for (player, client) in clients.iter_mut().enumerate() {
+            let player = player as u8;
+            let selected = match recipient {
+                Recipient::All => true,
+                Recipient::Player(p) => p == player,
+                Recipient::Others(p) => p != player,
+            };
+            let Some(sender) = client.as_ref().filter(|_| selected) else {
+                continue;
+            };
+            if sender.send(message.clone()).is_err() {
+                log::warn!("Player {player} can't be reached");
+                *client = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::components::{BoardCursor, MousePress};
+use crate::resources::{Board, BoardAssets, InputAction};
+use crate::ActionInput;
+use bevy::log;
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+
+/// Pause state of the running board, registered by the board plugin
+///
+/// Board systems only run while [`PauseState::Running`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default, States)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Pause settings, the pause is toggled by [`InputAction::Pause`]
+#[derive(Debug, Clone, Resource)]
+pub struct PauseOptions {
+    /// Pause when the window loses focus
+    pub pause_on_focus_loss: bool,
+}
+
+impl Default for PauseOptions {
+    fn default() -> Self {
+        Self {
+            pause_on_focus_loss: true,
+        }
+    }
+}
+
+/// Board visibility before the pause hid it
+#[derive(Debug, Component)]
+struct PausedVisibility(Visibility);
+
+/// Root node of the pause screen
+#[derive(Debug, Component)]
+struct PauseScreen;
+
+/// Pause of the running board: the game timer and inputs are stopped and the
+/// boards hidden until the game is resumed
+pub struct PausePlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: States> Plugin for PausePlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseOptions>()
+            .add_systems(
+                Update,
+                Self::toggle_pause.run_if(in_state(self.running_state.clone())),
+            )
+            .add_systems(OnEnter(PauseState::Paused), Self::hide_boards)
+            .add_systems(OnExit(PauseState::Paused), Self::show_boards)
+            .add_systems(OnExit(self.running_state.clone()), Self::resume);
+
+        log::info!("Loaded Pause Plugin");
+    }
+}
+
+impl<T> PausePlugin<T> {
+    /// Toggle the pause on the pause action, pause when the window loses focus
+    fn toggle_pause(
+        options: Res<PauseOptions>,
+        actions: ActionInput,
+        state: Res<State<PauseState>>,
+        mut next_state: ResMut<NextState<PauseState>>,
+        mut window_focused_event_reader: EventReader<WindowFocused>,
+    ) {
+        let focus_lost = window_focused_event_reader
+            .iter()
+            .any(|event| !event.focused);
+
+        let paused = *state.get() == PauseState::Paused;
+        if actions.just_pressed(InputAction::Pause) {
+            next_state.set(if paused {
+                PauseState::Running
+            } else {
+                PauseState::Paused
+            });
+        } else if focus_lost && options.pause_on_focus_loss && !paused {
+            next_state.set(PauseState::Paused);
+        }
+    }
+
+    /// Hide the boards so that they can't be studied while paused, and drop
+    /// held mouse presses
+    fn hide_boards(
+        mut commands: Commands,
+        board_assets: Res<BoardAssets>,
+        mut boards: Query<(Entity, &mut Visibility), With<Board>>,
+        presses: Query<Entity, (With<BoardCursor>, With<MousePress>)>,
+    ) {
+        for (entity, mut visibility) in boards.iter_mut() {
+            commands
+                .entity(entity)
+                .insert(PausedVisibility(*visibility));
+            *visibility = Visibility::Hidden;
+        }
+        for entity in presses.iter() {
+            commands.entity(entity).remove::<MousePress>();
+        }
+
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.),
+                    left: Val::Px(10.),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.75).into(),
+                ..default()
+            })
+            .insert(Name::new("Pause Screen"))
+            .insert(PauseScreen)
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "Paused",
+                    TextStyle {
+                        font: board_assets.bomb_counter_font.clone(),
+                        font_size: 14.,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+
+        log::info!("Game paused");
+    }
+
+    /// Restore the boards as they were before the pause
+    fn show_boards(
+        mut commands: Commands,
+        mut boards: Query<(Entity, &PausedVisibility, &mut Visibility)>,
+        screens: Query<Entity, With<PauseScreen>>,
+    ) {
+        for (entity, paused, mut visibility) in boards.iter_mut() {
+            *visibility = paused.0;
+            commands.entity(entity).remove::<PausedVisibility>();
+        }
+        for entity in screens.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        log::info!("Game resumed");
+    }
+
+    /// Leave the pause along with the running state
+    fn resume(mut next_state: ResMut<NextState<PauseState>>) {
+        next_state.set(PauseState::Running);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::resources::{InputMap, Preferences};
+use bevy::log;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Preferences storage options
+#[derive(Debug, Clone, Resource)]
+pub struct PreferencesOptions {
+    /// Local preferences file
+    pub path: PathBuf,
+}
+
+impl Default for PreferencesOptions {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("preferences.json"),
+        }
+    }
+}
+
+/// Loads the user [`Preferences`] and saves them whenever they change
+///
+/// The [`InputMap`] resource is loaded from the preferences, and copied back
+/// into them when rebound.
+pub struct PreferencesPlugin;
+
+impl Plugin for PreferencesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PreferencesOptions>()
+            .add_systems(Startup, Self::load_preferences)
+            .add_systems(
+                Update,
+                (Self::sync_bindings, Self::save_preferences)
+                    .chain()
+                    .run_if(resource_exists::<Preferences>()),
+            );
+
+        log::info!("Loaded Preferences Plugin");
+    }
+}
+
+impl PreferencesPlugin {
+    fn load_preferences(
+        mut commands: Commands,
+        options: Res<PreferencesOptions>,
+    ) {
+        let preferences = match Preferences::load(&options.path) {
+            Ok(preferences) => preferences,
+            Err(e) => {
+                log::error!("Failed to load preferences: {e}");
+                Preferences::default()
+            }
+        };
+        commands.insert_resource(preferences.bindings.clone());
+        commands.insert_resource(preferences);
+    }
+
+    fn sync_bindings(
+        input_map: Option<Res<InputMap>>,
+        mut preferences: ResMut<Preferences>,
+    ) {
+        let Some(input_map) = input_map.filter(|map| map.is_changed()) else {
+            return;
+        };
+        if preferences.bindings != *input_map {
+            preferences.bindings = input_map.clone();
+        }
+    }
+
+    fn save_preferences(
+        options: Res<PreferencesOptions>,
+        preferences: Res<Preferences>,
+    ) {
+        if !preferences.is_changed() || preferences.is_added() {
+            return;
+        }
+
+        if let Err(e) = preferences.save(&options.path) {
+            log::error!("Failed to save preferences: {e}");
+        }
+    }
+}
@@ -1,46 +1,39 @@
 use crate::bounds::Bounds2;
-use crate::{Coordinates, TileMap};
-use bevy::utils::HashMap;
+use crate::components::PlayerId;
+use crate::resources::tile::TileMark;
+use crate::resources::PlayerOptions;
+use crate::{Coordinates, Tile, TileMap};
+use bevy::utils::{HashMap, HashSet};
 use bevy::{log, prelude::*};
 
-#[derive(Debug, Resource)]
+/// Board state, stored on the board entity
+#[derive(Debug, Component)]
 pub struct Board {
     pub tile_map: TileMap,
     pub bounds: Bounds2,
     pub tile_size: f32,
-    pub entity: Entity,
     pub covered_tiles: HashMap<Coordinates, Entity>,
-    pub marked_tiles: Vec<Coordinates>,
+    /// Number of flags placed on each marked tile
+    pub marked_tiles: HashMap<Coordinates, u8>,
+    /// Tiles marked with a question mark, they don't block reveals
+    pub question_tiles: HashSet<Coordinates>,
+    /// Marks cycle through a question mark after the flags
+    pub question_marks: bool,
+    /// Flags are disabled
+    pub no_flag: bool,
+    /// Players of the board, with the options of their cursor
+    pub players: Vec<(PlayerId, PlayerOptions)>,
 }
 
 impl Board {
     /// Translate mouse position to board coordinates
     pub fn mouse_position(&self, window: &Window) -> Option<Coordinates> {
-        let Some(position) = window.cursor_position() else {
-            return None;
-        };
-        log::trace!("Mouse position: {}", position);
-
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size / 2.;
-        log::trace!("Adjusted position: {}", position);
-
-        if !self.bounds.in_bounds(position) {
-            return None;
-        }
-        log::trace!("In bounds {:?}", self.bounds);
-
-        let coordinates = position - self.bounds.position;
-
-        Some(Coordinates {
-            x: (coordinates.x / self.tile_size) as u16,
-            y: (coordinates.y / self.tile_size) as u16,
-        })
+        self.bounds.cursor_coordinates(window, self.tile_size)
     }
 
     /// Retrieve a covered tile entity
     pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        if self.marked_tiles.contains(coords) {
+        if self.marked_tiles.contains_key(coords) {
             None
         } else {
             self.covered_tiles.get(coords)
@@ -49,28 +42,75 @@ impl Board {
 
     /// Attempt to uncover a tile, returning the entity
     pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
-        if self.marked_tiles.contains(coords) {
+        if self.marked_tiles.contains_key(coords) {
             self.unmark_tile(coords)?;
         }
+        self.question_tiles.remove(coords);
         self.covered_tiles.remove(coords)
     }
 
+    /// Cycle the mark of a covered tile through `1..=max_bombs_per_tile`
+    /// flags, then a question mark when enabled, then back to unmarked,
+    /// returning the entity and its new mark
     pub fn try_toggle_mark(
         &mut self,
         coords: &Coordinates,
-    ) -> Option<(Entity, bool)> {
+    ) -> Option<(Entity, TileMark)> {
         let entity = *self.covered_tiles.get(coords)?;
-        let mark = self.marked_tiles.contains(coords);
+        let flags = self.marked_tiles.get(coords).copied().unwrap_or(0);
 
-        if mark {
+        let mark = if self.question_tiles.remove(coords) {
+            TileMark::None
+        } else if flags >= self.tile_map.max_bombs_per_tile() {
             self.unmark_tile(coords)?;
+            if self.question_marks {
+                self.question_tiles.insert(*coords);
+                TileMark::Question
+            } else {
+                TileMark::None
+            }
         } else {
-            self.marked_tiles.push(*coords);
+            self.marked_tiles.insert(*coords, flags + 1);
+            TileMark::Flag(flags + 1)
         };
 
         Some((entity, mark))
     }
 
+    /// Retrieve the covered tiles uncovered by chording on the revealed
+    /// number at `coords`, once as many flags as bombs surround it
+    pub fn chord_tiles(&self, coords: &Coordinates) -> Option<Vec<Entity>> {
+        if self.covered_tiles.contains_key(coords) {
+            return None;
+        }
+        let Some(Tile::BombNeighbor(count)) = self
+            .tile_map
+            .get(coords.y as usize)
+            .and_then(|line| line.get(coords.x as usize))
+        else {
+            return None;
+        };
+
+        let flags = self
+            .tile_map
+            .safe_square_at(*coords)
+            .filter_map(|c| self.marked_tiles.get(&c))
+            .fold(0, |acc: u8, flags| acc.saturating_add(*flags));
+        if flags != *count {
+            return None;
+        }
+
+        let tiles: Vec<_> = self
+            .tile_map
+            .safe_square_at(*coords)
+            .filter(|c| !self.marked_tiles.contains_key(c))
+            .filter_map(|c| self.covered_tiles.get(&c))
+            .copied()
+            .collect();
+
+        (!tiles.is_empty()).then_some(tiles)
+    }
+
     /// Retrieve adjacent covered tile entities of `coord`
     pub fn adjacent_covered_tiles(&self, coords: Coordinates) -> Vec<Entity> {
         self.tile_map
@@ -80,20 +120,81 @@ impl Board {
             .collect()
     }
 
-    /// Removes the `coords` from `marked_tiles`
-    pub fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Coordinates> {
-        self.marked_tiles
-            .iter()
-            .position(|a| a == coords)
-            .and_then(|pos| Some(self.marked_tiles.remove(pos)))
-            .or_else(|| {
-                log::error!("Failed to unmark tile at {}", coords);
-                None
-            })
+    /// Removes the `coords` from `marked_tiles`, returning its flag count
+    pub fn unmark_tile(&mut self, coords: &Coordinates) -> Option<u8> {
+        self.marked_tiles.remove(coords).or_else(|| {
+            log::error!("Failed to unmark tile at {}", coords);
+            None
+        })
+    }
+
+    /// Bombs left to flag, question marks don't count
+    pub fn remaining_bombs(&self) -> i32 {
+        let flags: u32 = self.marked_tiles.values().map(|v| *v as u32).sum();
+        self.tile_map.bomb_count() as i32 - flags as i32
     }
 
-    /// Is the board complete
+    /// Is the board complete, only tiles holding bombs are left covered
     pub fn is_completed(&self) -> bool {
-        self.tile_map.bomb_count() as usize == self.covered_tiles.len()
+        self.tile_map.bomb_tile_count() as usize == self.covered_tiles.len()
+    }
+
+    /// Short spoken label of the tile at `coords`, as the player sees it
+    pub fn tile_label(&self, coords: &Coordinates) -> String {
+        if self.covered_tiles.contains_key(coords) {
+            return match self.marked_tiles.get(coords) {
+                Some(1) => "flag".to_string(),
+                Some(flags) => format!("{flags} flags"),
+                None if self.question_tiles.contains(coords) => {
+                    "question mark".to_string()
+                }
+                None => "covered".to_string(),
+            };
+        }
+
+        match self
+            .tile_map
+            .get(coords.y as usize)
+            .and_then(|line| line.get(coords.x as usize))
+        {
+            Some(Tile::Bomb(_)) => "mine".to_string(),
+            Some(Tile::BombNeighbor(count)) => count.to_string(),
+            Some(Tile::Empty) => "empty".to_string(),
+            Some(Tile::Void) | None => "wall".to_string(),
+        }
+    }
+
+    /// Describe the tile at `coords` with the flags around it
+    pub fn describe_tile(&self, coords: &Coordinates) -> String {
+        let label = self.tile_label(coords);
+        if self.tile_map.is_void_at(*coords) {
+            return format!("{coords}: {label}");
+        }
+
+        let flags = self
+            .tile_map
+            .safe_square_at(*coords)
+            .filter_map(|c| self.marked_tiles.get(&c))
+            .fold(0u32, |acc, flags| acc + *flags as u32);
+        match flags {
+            0 => format!("{coords}: {label}"),
+            1 => format!("{coords}: {label}, 1 flag around"),
+            _ => format!("{coords}: {label}, {flags} flags around"),
+        }
+    }
+
+    /// Describe the board row by row, from the top row down
+    pub fn describe_rows(&self) -> Vec<String> {
+        let width = self.tile_map.width();
+
+        (0..self.tile_map.height())
+            .rev()
+            .map(|y| {
+                let tiles: Vec<_> = (0..width)
+                    .map(|x| self.tile_label(&Coordinates::new(x, y)))
+                    .collect();
+                format!("Row {y}: {}", tiles.join(", "))
+            })
+            .collect()
     }
 }
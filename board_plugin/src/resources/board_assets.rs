@@ -1,5 +1,10 @@
+use crate::components::BoardSprite;
+use crate::resources::{
+    AccessibilityPreferences, BoardSkin, CounterPalette, SkinTile,
+};
 use bevy::prelude::*;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::text::BreakLineOn;
 
 #[derive(Debug, Clone)]
 pub struct SpriteMaterial {
@@ -16,6 +21,9 @@ impl Default for SpriteMaterial {
     }
 }
 
+/// Brightness kept by counter colors on every cycle through the palette
+const COUNTER_CYCLE_SHADE: f32 = 0.6;
+
 /// Assets collection for the board
 #[derive(Debug, Clone, Resource)]
 pub struct BoardAssets {
@@ -26,10 +34,14 @@ pub struct BoardAssets {
     pub bomb_counter_font: Handle<Font>,
     pub bomb_counter_colors: Vec<Color>,
     pub flag_material: SpriteMaterial,
+    pub question_material: SpriteMaterial,
     pub bomb_material: SpriteMaterial,
+    /// Atlas used instead of the materials and counter texts when set
+    pub skin: Option<BoardSkin>,
 }
 
 impl BoardAssets {
+    /// One color per counter from 1 to 8
     pub fn default_colors() -> Vec<Color> {
         vec![
             Color::WHITE,
@@ -37,18 +49,183 @@ impl BoardAssets {
             Color::YELLOW,
             Color::ORANGE,
             Color::PURPLE,
+            Color::CYAN,
+            Color::PINK,
+            Color::RED,
         ]
     }
 
+    /// Copy of these assets following the accessibility preferences
+    ///
+    /// High contrast drops the skin so that every sprite gets the contrasted
+    /// materials, skinned counters otherwise keep the colors of the skin.
+    #[must_use]
+    pub fn accessible(&self, preferences: &AccessibilityPreferences) -> Self {
+        let mut assets = self.clone();
+
+        let palette = match preferences.palette {
+            CounterPalette::Theme if preferences.high_contrast => {
+                CounterPalette::OkabeIto
+            }
+            palette => palette,
+        };
+        if let Some(colors) = palette.colors() {
+            assets.bomb_counter_colors = colors.to_vec();
+        }
+
+        if preferences.high_contrast {
+            assets.board_material.color = Color::WHITE;
+            assets.tile_material.color = Color::BLACK;
+            assets.covered_tile_material.color = Color::rgb(0.75, 0.75, 0.75);
+            assets.flag_material.color = Color::BLACK;
+            assets.question_material.color = Color::BLACK;
+            assets.bomb_material.color = Color::WHITE;
+            assets.skin = None;
+        }
+
+        assets
+    }
+
     /// Retrieve color matching a bomb counter
+    ///
+    /// Counters past the end of `bomb_counter_colors` cycle through it again,
+    /// darker on every cycle so that each count keeps its own color.
     pub fn get_bomb_color(&self, counter: u8) -> Color {
-        let counter = counter.saturating_sub(1) as usize;
+        let colors = &self.bomb_counter_colors;
+        if colors.is_empty() {
+            return Color::WHITE;
+        }
+        let index = counter.saturating_sub(1) as usize;
+        let color = colors[index % colors.len()];
+        let cycle = (index / colors.len()) as i32;
+        if cycle == 0 {
+            return color;
+        }
+
+        let shade = COUNTER_CYCLE_SHADE.powi(cycle);
+        Color::rgba(
+            color.r() * shade,
+            color.g() * shade,
+            color.b() * shade,
+            color.a(),
+        )
+    }
+
+    /// Material drawing `role` without skin, `None` for counters which are
+    /// drawn as text
+    pub fn material(&self, role: BoardSprite) -> Option<SpriteMaterial> {
+        let material = match role {
+            BoardSprite::Background => &self.board_material,
+            // Held down covers look like the uncovered tile below
+            BoardSprite::Tile | BoardSprite::PressedCover => {
+                &self.tile_material
+            }
+            BoardSprite::Cover => &self.covered_tile_material,
+            BoardSprite::Flag { .. } => &self.flag_material,
+            BoardSprite::Question => &self.question_material,
+            BoardSprite::Bomb { .. } => &self.bomb_material,
+            BoardSprite::Counter(_) | BoardSprite::Count(_) => return None,
+        };
+        let color = match role {
+            BoardSprite::Flag { wrong: true, .. }
+            | BoardSprite::Bomb { exploded: true } => Color::RED,
+            BoardSprite::Flag {
+                tint: Some(tint), ..
+            } => tint,
+            _ => material.color,
+        };
+
+        Some(SpriteMaterial {
+            color,
+            texture: material.texture.clone(),
+        })
+    }
+
+    /// Sprite of `tile` in the selected skin, `None` without a skin or when
+    /// the skin has no cell for it
+    pub fn skin_sprite(
+        &self,
+        tile: SkinTile,
+        size: f32,
+    ) -> Option<SpriteSheetBundle> {
+        let skin = self.skin.as_ref()?;
+
+        Some(SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
+                index: tile.index()?,
+                custom_size: Some(Vec2::splat(size)),
+                ..default()
+            },
+            texture_atlas: skin.atlas.clone(),
+            ..default()
+        })
+    }
+
+    /// Generate bomb counter text 2D Bundle for a given value
+    pub fn bomb_count_text_bundle(&self, count: u8, size: f32) -> Text2dBundle {
+        let color = self.get_bomb_color(count);
+        let value = count.to_string();
+        // Shrink multi-digit counters from extended neighborhoods to fit
+        let font_size = size / value.len() as f32;
+
+        Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value,
+                    style: TextStyle {
+                        color,
+                        font: self.bomb_counter_font.clone(),
+                        font_size,
+                    },
+                }],
+                alignment: TextAlignment::Center,
+                linebreak_behavior: BreakLineOn::WordBoundary,
+            },
+            transform: Transform::from_xyz(0., 0., 1.),
+            ..default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assets() -> BoardAssets {
+        BoardAssets {
+            label: "Test".to_string(),
+            board_material: default(),
+            tile_material: default(),
+            covered_tile_material: default(),
+            bomb_counter_font: default(),
+            bomb_counter_colors: BoardAssets::default_colors(),
+            flag_material: default(),
+            question_material: default(),
+            bomb_material: default(),
+            skin: None,
+        }
+    }
+
+    #[test]
+    fn counters_past_the_palette_get_their_own_colors() {
+        let assets = assets();
+        let colors: Vec<_> =
+            (1..=24).map(|c| assets.get_bomb_color(c)).collect();
+
+        assert_eq!(colors[..8], BoardAssets::default_colors());
+        for (i, color) in colors.iter().enumerate() {
+            assert!(
+                !colors[..i].contains(color),
+                "counter {} shares its color",
+                i + 1
+            );
+        }
+    }
 
-        let color =
-            self.bomb_counter_colors.get(counter).unwrap_or_else(|| {
-                self.bomb_counter_colors.last().unwrap_or(&Color::WHITE)
-            });
+    #[test]
+    fn counters_past_the_palette_are_darker() {
+        let assets = assets();
 
-        *color
+        assert_eq!(assets.get_bomb_color(9), Color::rgb(0.6, 0.6, 0.6));
     }
 }
@@ -1,4 +1,7 @@
-use bevy::prelude::{default, Resource, Vec3};
+use crate::resources::{
+    tile_map::TileMap, BoardShape, Neighborhood, PlayStyle, TileMask,
+};
+use bevy::prelude::{default, Component, Resource, Vec3};
 use serde::{Deserialize, Serialize};
 
 /// Tile size options
@@ -32,8 +35,23 @@ impl Default for BoardPosition {
     }
 }
 
+/// Board edge topology
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize,
+)]
+pub enum Topology {
+    /// Edges are hard borders
+    #[default]
+    Bounded,
+    /// Edges wrap around to the opposite side, every tile has 8 neighbors
+    Toroidal,
+}
+
 /// Board generation options
-#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+///
+/// Inserted as a resource to configure the boards created when entering the
+/// running state, each board entity keeps a copy of its own options.
+#[derive(Debug, Clone, Serialize, Deserialize, Resource, Component)]
 pub struct BoardOptions {
     /// Tile map size (width, height)
     pub map_size: (u16, u16),
@@ -47,6 +65,25 @@ pub struct BoardOptions {
     pub tile_padding: f32,
     /// Does the board generate a safe place to start
     pub safe_start: bool,
+    /// Board edge topology
+    pub topology: Topology,
+    /// Tiles considered adjacent for bomb counts and flood fill
+    pub neighborhood: Neighborhood,
+    /// Board shape, void cells are never mined nor spawned
+    pub shape: BoardShape,
+    /// Maximum number of bombs a single tile can hold
+    pub max_bombs_per_tile: u8,
+    /// Seed of the bomb layout, random when `None`
+    pub seed: Option<u64>,
+    /// Disable flags entirely (NF play)
+    pub no_flag: bool,
+    /// Marks cycle through a question mark after the flags
+    pub question_marks: bool,
+    /// Handmade bomb layout replacing the seeded one, see
+    /// [`TileMap::set_bombs_at`]
+    #[cfg(test)]
+    #[serde(skip)]
+    pub layout: Option<Vec<crate::components::Coordinates>>,
 }
 
 impl Default for BoardOptions {
@@ -58,6 +95,53 @@ impl Default for BoardOptions {
             tile_size: default(),
             tile_padding: 0.,
             safe_start: false,
+            topology: default(),
+            neighborhood: default(),
+            shape: default(),
+            max_bombs_per_tile: 1,
+            seed: None,
+            no_flag: false,
+            question_marks: false,
+            #[cfg(test)]
+            layout: None,
+        }
+    }
+}
+
+impl BoardOptions {
+    /// Play style of the boards
+    #[inline]
+    #[must_use]
+    pub fn style(&self) -> PlayStyle {
+        if self.no_flag {
+            PlayStyle::NoFlag
+        } else {
+            PlayStyle::Flagging
         }
     }
+
+    /// Generate the tile map described by the options, with a layout
+    /// derived from `seed`
+    #[must_use]
+    pub fn tile_map(&self, seed: u64) -> TileMap {
+        let mask = self.shape.mask(self.map_size);
+        let (width, height) =
+            mask.as_ref().map_or(self.map_size, TileMask::size);
+
+        let mut tile_map = TileMap::empty(width, height)
+            .with_topology(self.topology)
+            .with_neighborhood(&self.neighborhood)
+            .with_max_bombs_per_tile(self.max_bombs_per_tile);
+        if let Some(mask) = &mask {
+            tile_map.apply_mask(mask);
+        }
+        #[cfg(test)]
+        if let Some(layout) = &self.layout {
+            tile_map.set_bombs_at(layout);
+            return tile_map;
+        }
+        tile_map.set_bombs_seeded(self.bomb_count, seed);
+
+        tile_map
+    }
 }
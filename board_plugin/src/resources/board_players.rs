@@ -0,0 +1,68 @@
+use crate::components::{BoardInput, PlayerId};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Default player colors, indexed by player id
+pub const PLAYER_COLORS: [Color; 4] = [
+    Color::rgba(1., 0.8, 0., 0.5),
+    Color::rgba(0., 0.6, 1., 0.5),
+    Color::rgba(0.9, 0.2, 0.6, 0.5),
+    Color::rgba(0.2, 0.8, 0.3, 0.5),
+];
+
+/// Multiplayer game mode
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize,
+)]
+pub enum GameMode {
+    /// Every player has their own copy of the board, first to clear it wins
+    #[default]
+    Race,
+    /// Every player acts on the same board
+    Coop,
+}
+
+/// Local multiplayer setup
+///
+/// Without this resource a single mouse controlled board is spawned.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct BoardPlayers {
+    pub players: Vec<PlayerOptions>,
+    /// One board per player in race, a single shared board in coop
+    pub mode: GameMode,
+}
+
+impl BoardPlayers {
+    /// Color of `player`, falling back to the default palette
+    #[must_use]
+    pub fn color(&self, player: PlayerId) -> Color {
+        self.players
+            .get(player.0 as usize)
+            .map_or_else(|| player_color(player), |p| p.color)
+    }
+}
+
+/// Options of a single player
+#[derive(Debug, Clone)]
+pub struct PlayerOptions {
+    /// Input device driving the player cursor
+    pub input: BoardInput,
+    /// Color of the player cursor and flags
+    pub color: Color,
+}
+
+impl Default for PlayerOptions {
+    fn default() -> Self {
+        Self {
+            input: default(),
+            color: PLAYER_COLORS[0],
+        }
+    }
+}
+
+/// Default color of `player`
+#[inline]
+#[must_use]
+pub fn player_color(player: PlayerId) -> Color {
+    PLAYER_COLORS[player.0 as usize % PLAYER_COLORS.len()]
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+/// Number of columns of a skin atlas
+pub const SKIN_COLUMNS: usize = 8;
+/// Number of rows of a skin atlas
+pub const SKIN_ROWS: usize = 2;
+
+/// Cell of a skin atlas
+///
+/// The first row holds the tile states in declaration order, the second one
+/// the numbers from 1 to 8.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SkinTile {
+    /// Raised cover
+    Covered,
+    /// Cover held down
+    Pressed,
+    /// Empty uncovered tile
+    Uncovered,
+    Flag,
+    Question,
+    Mine,
+    /// Flag placed on a tile without bomb, shown once the game is lost
+    WrongFlag,
+    /// Mine that blew up
+    ExplodedMine,
+    /// Bomb counter
+    Number(u8),
+}
+
+impl SkinTile {
+    /// Index of the cell in the atlas, `None` for numbers past 8
+    #[must_use]
+    pub fn index(self) -> Option<usize> {
+        let index = match self {
+            Self::Covered => 0,
+            Self::Pressed => 1,
+            Self::Uncovered => 2,
+            Self::Flag => 3,
+            Self::Question => 4,
+            Self::Mine => 5,
+            Self::WrongFlag => 6,
+            Self::ExplodedMine => 7,
+            Self::Number(n @ 1..=8) => SKIN_COLUMNS + n as usize - 1,
+            Self::Number(_) => return None,
+        };
+        Some(index)
+    }
+}
+
+/// Texture atlas skin of the board
+#[derive(Debug, Clone)]
+pub struct BoardSkin {
+    pub label: String,
+    pub atlas: Handle<TextureAtlas>,
+}
+
+impl BoardSkin {
+    /// Atlas of a skin image laid out on the skin grid, with square cells of
+    /// `cell_size` pixels
+    #[must_use]
+    pub fn atlas(texture: Handle<Image>, cell_size: f32) -> TextureAtlas {
+        TextureAtlas::from_grid(
+            texture,
+            Vec2::splat(cell_size),
+            SKIN_COLUMNS,
+            SKIN_ROWS,
+            None,
+            None,
+        )
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+/// Sounds played on board events, silent when unset
+#[derive(Debug, Clone, Default, Resource)]
+pub struct BoardSounds {
+    /// Tile uncovered by a click
+    pub reveal: Handle<AudioSource>,
+    /// Tiles uncovered by a cascade, throttled
+    pub cascade: Handle<AudioSource>,
+    pub flag: Handle<AudioSource>,
+    /// Flag removed or turned into a question mark
+    pub unflag: Handle<AudioSource>,
+    pub chord: Handle<AudioSource>,
+    pub explosion: Handle<AudioSource>,
+    pub win: Handle<AudioSource>,
+}
@@ -0,0 +1,249 @@
+use crate::components::Coordinates;
+use crate::resources::{
+    tile::{Tile, TileMark},
+    tile_map::TileMap,
+};
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+/// Tile uncovered by a player action
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RevealedTile {
+    pub coordinates: Coordinates,
+    pub tile: Tile,
+}
+
+/// Result of a reveal action on a [`BoardState`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RevealOutcome {
+    /// Safe tiles uncovered, including the cascade
+    Revealed(Vec<RevealedTile>),
+    /// The tile held a bomb, after uncovering the safe tiles of the same
+    /// chord
+    Exploded {
+        revealed: Vec<RevealedTile>,
+        tile: RevealedTile,
+    },
+    /// The action isn't allowed on this tile
+    Invalid,
+}
+
+/// Headless board state, validating player actions against the tile map
+///
+/// Unlike [`crate::resources::Board`] it doesn't track any entity, so it can
+/// run without a world (e.g. on a game server).
+#[derive(Debug, Clone)]
+pub struct BoardState {
+    tile_map: TileMap,
+    covered: HashSet<Coordinates>,
+    marked: HashMap<Coordinates, u8>,
+    questioned: HashSet<Coordinates>,
+    question_marks: bool,
+    no_flag: bool,
+    exploded: bool,
+}
+
+impl BoardState {
+    /// Create a fully covered board state
+    pub fn new(tile_map: TileMap) -> Self {
+        let covered = (0..tile_map.height())
+            .flat_map(|y| {
+                (0..tile_map.width()).map(move |x| Coordinates { x, y })
+            })
+            .filter(|coords| !tile_map.is_void_at(*coords))
+            .collect();
+
+        Self {
+            tile_map,
+            covered,
+            marked: HashMap::new(),
+            questioned: HashSet::new(),
+            question_marks: false,
+            no_flag: false,
+            exploded: false,
+        }
+    }
+
+    /// Cycle marks through a question mark after the flags
+    #[inline]
+    #[must_use]
+    pub fn with_question_marks(mut self, question_marks: bool) -> Self {
+        self.question_marks = question_marks;
+        self
+    }
+
+    /// Refuse every mark (NF play)
+    #[inline]
+    #[must_use]
+    pub fn with_no_flag(mut self, no_flag: bool) -> Self {
+        self.no_flag = no_flag;
+        self
+    }
+
+    /// Uncover the tile at `coords`, flood filling through empty tiles
+    ///
+    /// Revealing an uncovered number chords it, see [`BoardState::chord`].
+    pub fn reveal(&mut self, coords: Coordinates) -> RevealOutcome {
+        if self.is_finished() || self.marked.contains_key(&coords) {
+            return RevealOutcome::Invalid;
+        }
+        if !self.covered.contains(&coords) {
+            return self.chord(coords);
+        }
+
+        let tile = self.tile_at(coords);
+        if tile.is_bomb() {
+            self.covered.remove(&coords);
+            self.exploded = true;
+            return RevealOutcome::Exploded {
+                revealed: Vec::new(),
+                tile: RevealedTile {
+                    coordinates: coords,
+                    tile,
+                },
+            };
+        }
+
+        let mut revealed = Vec::new();
+        let mut stack = vec![coords];
+
+        while let Some(coords) = stack.pop() {
+            if !self.covered.remove(&coords) {
+                continue;
+            }
+            self.marked.remove(&coords);
+            self.questioned.remove(&coords);
+
+            let tile = self.tile_at(coords);
+            revealed.push(RevealedTile {
+                coordinates: coords,
+                tile,
+            });
+
+            if tile == Tile::Empty {
+                stack.extend(
+                    self.tile_map
+                        .safe_square_at(coords)
+                        .filter(|c| self.covered.contains(c)),
+                );
+            }
+        }
+
+        RevealOutcome::Revealed(revealed)
+    }
+
+    /// Uncover the unmarked neighbors of the revealed number at `coords`,
+    /// once as many flags as bombs surround it
+    pub fn chord(&mut self, coords: Coordinates) -> RevealOutcome {
+        let Tile::BombNeighbor(count) = self.tile_at(coords) else {
+            return RevealOutcome::Invalid;
+        };
+        if self.is_finished() || self.covered.contains(&coords) {
+            return RevealOutcome::Invalid;
+        }
+
+        let flags = self
+            .tile_map
+            .safe_square_at(coords)
+            .map(|c| self.flags_at(c))
+            .fold(0, u8::saturating_add);
+        if flags != count {
+            return RevealOutcome::Invalid;
+        }
+
+        let targets: Vec<_> = self
+            .tile_map
+            .safe_square_at(coords)
+            .filter(|c| {
+                self.covered.contains(c) && !self.marked.contains_key(c)
+            })
+            .collect();
+
+        let mut revealed = Vec::new();
+        for target in targets {
+            match self.reveal(target) {
+                RevealOutcome::Revealed(tiles) => revealed.extend(tiles),
+                RevealOutcome::Exploded { tile, .. } => {
+                    return RevealOutcome::Exploded { revealed, tile }
+                }
+                RevealOutcome::Invalid => (),
+            }
+        }
+
+        if revealed.is_empty() {
+            RevealOutcome::Invalid
+        } else {
+            RevealOutcome::Revealed(revealed)
+        }
+    }
+
+    /// Cycle the mark of a covered tile, returning its new mark
+    pub fn toggle_mark(&mut self, coords: Coordinates) -> Option<TileMark> {
+        if self.no_flag || self.is_finished() || !self.covered.contains(&coords)
+        {
+            return None;
+        }
+
+        let flags = self.flags_at(coords);
+        if self.questioned.remove(&coords) {
+            Some(TileMark::None)
+        } else if flags >= self.tile_map.max_bombs_per_tile() {
+            self.marked.remove(&coords);
+            if self.question_marks {
+                self.questioned.insert(coords);
+                Some(TileMark::Question)
+            } else {
+                Some(TileMark::None)
+            }
+        } else {
+            self.marked.insert(coords, flags + 1);
+            Some(TileMark::Flag(flags + 1))
+        }
+    }
+
+    /// Tile at `coords`, [`Tile::Void`] out of the map
+    pub fn tile_at(&self, coords: Coordinates) -> Tile {
+        self.tile_map
+            .get(coords.y as usize)
+            .and_then(|line| line.get(coords.x as usize))
+            .copied()
+            .unwrap_or(Tile::Void)
+    }
+
+    /// Is the board complete, only tiles holding bombs are left covered
+    pub fn is_completed(&self) -> bool {
+        !self.exploded
+            && self.tile_map.bomb_tile_count() as usize == self.covered.len()
+    }
+
+    /// Did a bomb explode
+    pub fn is_exploded(&self) -> bool {
+        self.exploded
+    }
+
+    /// Is the game over, either completed or exploded
+    pub fn is_finished(&self) -> bool {
+        self.exploded || self.is_completed()
+    }
+
+    /// Check if the tile at `coords` is covered
+    pub fn is_covered(&self, coords: Coordinates) -> bool {
+        self.covered.contains(&coords)
+    }
+
+    /// Number of covered tiles without bombs
+    pub fn remaining_safe_tiles(&self) -> u16 {
+        (self.covered.len() as u16)
+            .saturating_sub(self.tile_map.bomb_tile_count())
+    }
+
+    /// Number of flags on the tile at `coords`
+    pub fn flags_at(&self, coords: Coordinates) -> u8 {
+        self.marked.get(&coords).copied().unwrap_or(0)
+    }
+
+    /// Getter for `tile_map`
+    pub(crate) fn tile_map(&self) -> &TileMap {
+        &self.tile_map
+    }
+}
@@ -0,0 +1,222 @@
+use crate::resources::{tile::Tile, SQUARE_COORDINATES};
+use bevy::math::IVec2;
+use bevy::utils::HashMap;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Radius around the origin that never holds bombs, used as a safe start
+const SAFE_RADIUS: i32 = 1;
+
+/// Unbounded tile map generated lazily, one chunk at a time
+///
+/// Every chunk is generated from its own RNG seeded from the map seed and
+/// the chunk coordinates, so the layout doesn't depend on generation order.
+#[derive(Debug, Clone)]
+pub struct ChunkMap {
+    seed: u64,
+    chunk_size: u16,
+    bomb_density: f32,
+    chunks: HashMap<IVec2, Vec<bool>>,
+}
+
+impl ChunkMap {
+    /// Create an empty map, no chunk is generated yet
+    #[inline]
+    #[must_use]
+    pub fn new(seed: u64, chunk_size: u16, bomb_density: f32) -> Self {
+        Self {
+            seed,
+            chunk_size: chunk_size.max(1),
+            bomb_density: bomb_density.clamp(0., 1.),
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Chunk coordinates containing the `tile` coordinates
+    #[inline]
+    #[must_use]
+    pub fn chunk_of(&self, tile: IVec2) -> IVec2 {
+        let size = self.chunk_size as i32;
+        IVec2::new(tile.x.div_euclid(size), tile.y.div_euclid(size))
+    }
+
+    /// Coordinates of the bottom left tile of `chunk`
+    #[inline]
+    #[must_use]
+    pub fn chunk_origin(&self, chunk: IVec2) -> IVec2 {
+        chunk * self.chunk_size as i32
+    }
+
+    /// Iterator over every tile coordinates of `chunk`
+    pub fn chunk_tiles(&self, chunk: IVec2) -> impl Iterator<Item = IVec2> {
+        let origin = self.chunk_origin(chunk);
+        let size = self.chunk_size as i32;
+
+        (0..size).flat_map(move |y| {
+            (0..size).map(move |x| origin + IVec2::new(x, y))
+        })
+    }
+
+    /// Check if the tile at `tile` is a bomb, generating its chunk if needed
+    pub fn is_bomb_at(&mut self, tile: IVec2) -> bool {
+        let chunk = self.chunk_of(tile);
+        let local = tile - self.chunk_origin(chunk);
+        let index = (local.y * self.chunk_size as i32 + local.x) as usize;
+
+        self.generate_chunk(chunk)[index]
+    }
+
+    /// Count the number of adjacent tiles that are bombs
+    pub fn bomb_count_at(&mut self, tile: IVec2) -> u8 {
+        Self::square_at(tile)
+            .filter(|neighbor| self.is_bomb_at(*neighbor))
+            .count() as u8
+    }
+
+    /// Get the tile at `tile`, generating the chunks it depends on
+    pub(crate) fn tile_at(&mut self, tile: IVec2) -> Tile {
+        if self.is_bomb_at(tile) {
+            return Tile::Bomb(1);
+        }
+        match self.bomb_count_at(tile) {
+            0 => Tile::Empty,
+            v => Tile::BombNeighbor(v),
+        }
+    }
+
+    /// Get an iterator of tiles adjacent to `tile`
+    #[inline]
+    pub fn square_at(tile: IVec2) -> impl Iterator<Item = IVec2> {
+        SQUARE_COORDINATES
+            .iter()
+            .map(move |(x, y)| tile + IVec2::new(*x as i32, *y as i32))
+    }
+
+    fn generate_chunk(&mut self, chunk: IVec2) -> &[bool] {
+        let Self {
+            seed,
+            chunk_size,
+            bomb_density,
+            ..
+        } = *self;
+
+        self.chunks.entry(chunk).or_insert_with(|| {
+            let mut rng = ChaCha8Rng::seed_from_u64(chunk_seed(seed, chunk));
+            let size = chunk_size as i32;
+            let origin = chunk * size;
+
+            (0..size)
+                .flat_map(|y| (0..size).map(move |x| IVec2::new(x, y)))
+                .map(|local| {
+                    // Always draw to keep the sequence independent of the
+                    // safe area
+                    let bomb = rng.gen_bool(bomb_density as f64);
+                    let tile = origin + local;
+                    let safe = tile.x.abs() <= SAFE_RADIUS
+                        && tile.y.abs() <= SAFE_RADIUS;
+                    bomb && !safe
+                })
+                .collect()
+        })
+    }
+
+    /// Getter for `seed`
+    #[inline]
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Getter for `chunk_size`
+    #[inline]
+    #[must_use]
+    pub fn chunk_size(&self) -> u16 {
+        self.chunk_size
+    }
+}
+
+/// Mix the map seed with chunk coordinates (splitmix64 finalizer)
+fn chunk_seed(seed: u64, chunk: IVec2) -> u64 {
+    let coords = ((chunk.x as u32 as u64) << 32) | chunk.y as u32 as u64;
+    let mut z = (seed ^ coords).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 42;
+
+    /// Bomb layout of the chunks from `-2` to `2` on both axes
+    fn bombs(chunk_map: &mut ChunkMap) -> Vec<bool> {
+        let size = chunk_map.chunk_size() as i32;
+        (-2 * size..3 * size)
+            .flat_map(|y| (-2 * size..3 * size).map(move |x| IVec2::new(x, y)))
+            .map(|tile| chunk_map.is_bomb_at(tile))
+            .collect()
+    }
+
+    #[test]
+    fn chunks_do_not_depend_on_generation_order() {
+        let chunks: Vec<IVec2> = (-2..3)
+            .flat_map(|y| (-2..3).map(move |x| IVec2::new(x, y)))
+            .collect();
+        let mut forward = ChunkMap::new(SEED, 8, 0.3);
+        let mut backward = ChunkMap::new(SEED, 8, 0.3);
+
+        for chunk in &chunks {
+            forward.generate_chunk(*chunk);
+        }
+        for chunk in chunks.iter().rev() {
+            backward.generate_chunk(*chunk);
+        }
+
+        let layout = bombs(&mut forward);
+        assert!(layout.contains(&true));
+        assert!(layout.contains(&false));
+        assert_eq!(layout, bombs(&mut backward));
+    }
+
+    #[test]
+    fn counts_across_chunk_borders_do_not_depend_on_generation_order() {
+        let mut lazy = ChunkMap::new(SEED, 4, 0.3);
+        let mut eager = ChunkMap::new(SEED, 4, 0.3);
+        bombs(&mut eager);
+
+        // Tiles on the corners of chunks, counting bombs of 4 chunks
+        for tile in [IVec2::new(3, 3), IVec2::new(-1, -1), IVec2::new(4, -5)] {
+            assert_eq!(lazy.tile_at(tile), eager.tile_at(tile), "{tile}");
+        }
+    }
+
+    #[test]
+    fn seeds_give_different_layouts() {
+        let mut a = ChunkMap::new(SEED, 8, 0.3);
+        let mut b = ChunkMap::new(SEED + 1, 8, 0.3);
+
+        assert_ne!(bombs(&mut a), bombs(&mut b));
+    }
+
+    #[test]
+    fn start_area_is_safe() {
+        let mut chunk_map = ChunkMap::new(SEED, 4, 1.);
+
+        for tile in ChunkMap::square_at(IVec2::ZERO).chain([IVec2::ZERO]) {
+            assert!(!chunk_map.is_bomb_at(tile), "{tile}");
+        }
+        assert!(chunk_map.is_bomb_at(IVec2::new(2, 0)));
+        assert!(chunk_map.is_bomb_at(IVec2::new(-2, -2)));
+    }
+
+    #[test]
+    fn negative_tiles_belong_to_negative_chunks() {
+        let chunk_map = ChunkMap::new(SEED, 4, 0.3);
+
+        assert_eq!(chunk_map.chunk_of(IVec2::new(-1, 0)), IVec2::new(-1, 0));
+        assert_eq!(chunk_map.chunk_of(IVec2::new(-4, -5)), IVec2::new(-1, -2));
+        assert_eq!(chunk_map.chunk_of(IVec2::new(3, 4)), IVec2::new(0, 1));
+    }
+}
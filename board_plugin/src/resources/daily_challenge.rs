@@ -0,0 +1,197 @@
+use crate::resources::{BoardOptions, GameOutcome, GameStats};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Daily challenge tile map size, identical for everyone
+pub const DAILY_MAP_SIZE: (u16, u16) = (16, 16);
+
+/// Daily challenge bomb count, identical for everyone
+pub const DAILY_BOMB_COUNT: u16 = 40;
+
+/// Calendar date (UTC)
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ChallengeDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl ChallengeDate {
+    /// Current UTC date
+    #[must_use]
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self::from_days_since_epoch((seconds / 86_400) as i64)
+    }
+
+    /// Date of the given day count since 1970-01-01
+    #[must_use]
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's `civil_from_days` algorithm
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460
+            + day_of_era / 36_524
+            - day_of_era / 146_096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Layout seed of the date, FNV-1a hash of its text form
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.to_string()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+                (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+}
+
+impl Display for ChallengeDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Result of a daily challenge, stored locally
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub date: ChallengeDate,
+    pub seed: u64,
+    pub won: bool,
+    /// Time in seconds
+    pub time: f32,
+    pub bbbv: u16,
+    pub clicks: u32,
+}
+
+impl DailyResult {
+    /// Shareable result summary
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let outcome = if self.won { "cleared" } else { "exploded" };
+        let bbbv_per_second = if self.won && self.time > 0. {
+            self.bbbv as f32 / self.time
+        } else {
+            0.
+        };
+
+        format!(
+            "Minesweeper daily {}: {outcome}\n\
+             Time {:.1}s | 3BV {} | {bbbv_per_second:.2} 3BV/s | {} clicks",
+            self.date, self.time, self.bbbv, self.clicks
+        )
+    }
+
+    /// Append the result to the JSON lines file at `path`
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut file =
+            OpenOptions::new().create(true).append(true).open(path)?;
+        serde_json::to_writer(&mut file, self)?;
+        file.write_all(b"\n")
+    }
+
+    /// Load every result stored at `path`, skipping invalid lines
+    pub fn load_all(path: &Path) -> io::Result<Vec<Self>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+/// Daily challenge setup, the board is only a daily one while it exists
+#[derive(Debug, Clone, Resource)]
+pub struct DailyChallenge {
+    pub date: ChallengeDate,
+    /// Local results file, one JSON result per line
+    pub results_path: PathBuf,
+    /// Result of the current game, once over
+    pub result: Option<DailyResult>,
+}
+
+impl DailyChallenge {
+    /// Challenge of the given date
+    #[must_use]
+    pub fn new(date: ChallengeDate) -> Self {
+        Self {
+            date,
+            results_path: PathBuf::from("daily_results.jsonl"),
+            result: None,
+        }
+    }
+
+    /// Challenge of the current UTC date
+    #[must_use]
+    pub fn today() -> Self {
+        Self::new(ChallengeDate::today())
+    }
+
+    /// Layout seed of the challenge
+    #[inline]
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.date.seed()
+    }
+
+    /// Options of the daily board, only presentation settings are kept
+    /// from `base` so that every player gets the same layout
+    #[must_use]
+    pub fn board_options(&self, base: &BoardOptions) -> BoardOptions {
+        BoardOptions {
+            map_size: DAILY_MAP_SIZE,
+            bomb_count: DAILY_BOMB_COUNT,
+            position: base.position.clone(),
+            tile_size: base.tile_size.clone(),
+            tile_padding: base.tile_padding,
+            no_flag: base.no_flag,
+            question_marks: base.question_marks,
+            safe_start: true,
+            seed: Some(self.seed()),
+            ..default()
+        }
+    }
+
+    /// Build the result of a finished game
+    #[must_use]
+    pub fn result(&self, stats: &GameStats) -> Option<DailyResult> {
+        let outcome = stats.outcome?;
+
+        Some(DailyResult {
+            date: self.date,
+            seed: self.seed(),
+            won: outcome == GameOutcome::Won,
+            time: stats.elapsed.as_secs_f32(),
+            bbbv: stats.bbbv,
+            clicks: stats.clicks.total(),
+        })
+    }
+}
@@ -0,0 +1,141 @@
+use crate::resources::{chunk_map::ChunkMap, tile::Tile};
+use bevy::math::IVec2;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Maximum number of tiles uncovered by a cascade in a single frame, the
+/// rest of it is uncovered on the next frames
+const MAX_CASCADE: usize = 4096;
+
+/// Endless board generation options
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct EndlessOptions {
+    /// Seed of the procedural generation
+    pub seed: u64,
+    /// Width and height of a chunk, in tiles
+    pub chunk_size: u16,
+    /// Probability of a tile being a bomb
+    pub bomb_density: f32,
+    /// Size of each individual tile
+    pub tile_size: f32,
+    /// Padding between tiles
+    pub tile_padding: f32,
+}
+
+impl Default for EndlessOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            chunk_size: 16,
+            bomb_density: 0.16,
+            tile_size: 32.,
+            tile_padding: 1.,
+        }
+    }
+}
+
+/// Result of revealing a tile of the endless board
+#[derive(Debug, Clone, PartialEq)]
+pub enum RevealResult {
+    /// Safe tiles uncovered, including the cascade
+    Safe(Vec<IVec2>),
+    /// The tile was a bomb
+    Exploded,
+    /// The tile was already uncovered, flagged or the game is over
+    Ignored,
+}
+
+#[derive(Debug, Resource)]
+pub struct EndlessBoard {
+    pub chunk_map: ChunkMap,
+    pub tile_size: f32,
+    pub tile_padding: f32,
+    pub entity: Entity,
+    pub revealed: HashSet<IVec2>,
+    pub flagged: HashSet<IVec2>,
+    /// Entities of the currently spawned chunks
+    pub chunk_entities: HashMap<IVec2, Entity>,
+    /// Cover entities of the currently spawned covered tiles
+    pub covers: HashMap<IVec2, Entity>,
+    /// Tiles left to flood fill by cascades cut short at [`MAX_CASCADE`]
+    pub pending: VecDeque<IVec2>,
+    pub lost: bool,
+}
+
+impl EndlessBoard {
+    /// Translate a world position to tile coordinates
+    pub fn tile_at_position(&self, position: Vec2) -> IVec2 {
+        (position / self.tile_size + Vec2::splat(0.5))
+            .floor()
+            .as_ivec2()
+    }
+
+    /// World position of the center of `tile`
+    pub fn tile_position(&self, tile: IVec2) -> Vec2 {
+        tile.as_vec2() * self.tile_size
+    }
+
+    /// Reveal `tile`, flood filling through empty tiles
+    pub fn reveal(&mut self, tile: IVec2) -> RevealResult {
+        if self.lost
+            || self.revealed.contains(&tile)
+            || self.flagged.contains(&tile)
+        {
+            return RevealResult::Ignored;
+        }
+        if self.chunk_map.is_bomb_at(tile) {
+            self.lost = true;
+            return RevealResult::Exploded;
+        }
+
+        self.pending.push_front(tile);
+        RevealResult::Safe(self.continue_cascade())
+    }
+
+    /// Flood fill the pending cascades through empty tiles, uncovering at
+    /// most [`MAX_CASCADE`] tiles and leaving the rest pending
+    pub fn continue_cascade(&mut self) -> Vec<IVec2> {
+        let mut uncovered = Vec::new();
+
+        while uncovered.len() < MAX_CASCADE {
+            let Some(tile) = self.pending.pop_front() else {
+                break;
+            };
+            if self.flagged.contains(&tile) || !self.revealed.insert(tile) {
+                continue;
+            }
+            uncovered.push(tile);
+
+            if self.chunk_map.tile_at(tile) != Tile::Empty {
+                continue;
+            }
+            self.pending
+                .extend(ChunkMap::square_at(tile).filter(|neighbor| {
+                    !self.revealed.contains(neighbor)
+                        && !self.flagged.contains(neighbor)
+                }));
+        }
+
+        uncovered
+    }
+
+    /// Toggle a flag on a covered tile, returning whether it is now flagged
+    pub fn try_toggle_flag(&mut self, tile: IVec2) -> Option<bool> {
+        if self.lost || self.revealed.contains(&tile) {
+            return None;
+        }
+        if self.flagged.remove(&tile) {
+            Some(false)
+        } else {
+            self.flagged.insert(tile);
+            Some(true)
+        }
+    }
+
+    /// Number of safe tiles revealed so far
+    pub fn score(&self) -> usize {
+        self.revealed.len()
+    }
+}
@@ -0,0 +1,304 @@
+use crate::components::BoardId;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::AddAssign;
+use std::time::Duration;
+
+/// How a game ended
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameOutcome {
+    /// A board was completed
+    Won,
+    /// A bomb exploded
+    Lost,
+}
+
+/// Play style, stats are kept separately for each
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum PlayStyle {
+    /// Flags can be placed
+    #[default]
+    Flagging,
+    /// Flags are disabled
+    NoFlag,
+}
+
+impl Display for PlayStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flagging => write!(f, "flagging"),
+            Self::NoFlag => write!(f, "no flag"),
+        }
+    }
+}
+
+/// Kind of tile action
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ClickKind {
+    /// Uncover a covered tile
+    Left,
+    /// Mark a covered tile
+    Right,
+    /// Uncover the neighbors of a revealed number
+    Chord,
+}
+
+/// Tile action counts
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize,
+)]
+pub struct ClickStats {
+    pub left: u32,
+    pub right: u32,
+    pub chord: u32,
+    /// Actions that didn't change the board, of any kind
+    pub wasted: u32,
+}
+
+impl ClickStats {
+    /// Register an action
+    pub fn add(&mut self, kind: ClickKind, effective: bool) {
+        match kind {
+            ClickKind::Left => self.left += 1,
+            ClickKind::Right => self.right += 1,
+            ClickKind::Chord => self.chord += 1,
+        }
+        if !effective {
+            self.wasted += 1;
+        }
+    }
+
+    /// Number of actions of any kind
+    #[inline]
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.left + self.right + self.chord
+    }
+
+    /// Number of actions that changed the board
+    #[inline]
+    #[must_use]
+    pub fn effective(&self) -> u32 {
+        self.total().saturating_sub(self.wasted)
+    }
+}
+
+impl AddAssign for ClickStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.left += rhs.left;
+        self.right += rhs.right;
+        self.chord += rhs.chord;
+        self.wasted += rhs.wasted;
+    }
+}
+
+/// Timer, click counts and outcome of a single board
+///
+/// The timer starts on the first action on the board and stops once it ends.
+#[derive(Debug, Clone, Default)]
+pub struct BoardStats {
+    /// Time spent since the first action on the board
+    pub elapsed: Duration,
+    pub clicks: ClickStats,
+    /// 3BV of the board, minimum number of clicks needed to clear it
+    pub bbbv: u16,
+    /// Set once the board is over
+    pub outcome: Option<GameOutcome>,
+    started: bool,
+}
+
+impl BoardStats {
+    /// Create stats for a new board of the given 3BV
+    #[must_use]
+    pub fn new(bbbv: u16) -> Self {
+        Self { bbbv, ..default() }
+    }
+
+    /// Is the timer of the board running
+    #[inline]
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.started && self.outcome.is_none()
+    }
+
+    /// 3BV solved per second, only meaningful once the board is won
+    #[must_use]
+    pub fn bbbv_per_second(&self) -> f32 {
+        bbbv_per_second(self.bbbv, self.elapsed)
+    }
+
+    /// 3BV per effective click, 1 when every click was necessary
+    #[must_use]
+    pub fn efficiency(&self) -> f32 {
+        efficiency(self.bbbv, &self.clicks)
+    }
+}
+
+/// Timer and click counts of the current game, along with those of each of
+/// its boards
+///
+/// The timer starts on the first player action and stops once every board
+/// ended. Clicks and 3BV add up the ones of every board.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct GameStats {
+    /// Time spent since the first action
+    pub elapsed: Duration,
+    pub clicks: ClickStats,
+    /// Sum of the 3BV of the boards
+    pub bbbv: u16,
+    pub style: PlayStyle,
+    /// Set once every board is over, won if any of them was cleared
+    pub outcome: Option<GameOutcome>,
+    /// Number of players, only solo games are kept in the stats history
+    pub players: usize,
+    boards: BTreeMap<BoardId, BoardStats>,
+    started: bool,
+}
+
+impl GameStats {
+    /// Create stats for a new game of `players`, without any board
+    #[must_use]
+    pub fn new(style: PlayStyle, players: usize) -> Self {
+        Self {
+            style,
+            players,
+            ..default()
+        }
+    }
+
+    /// Register a new board of the given 3BV, replacing the stats of a
+    /// restarted one
+    ///
+    /// The game goes on until the new board ends.
+    pub fn add_board(&mut self, board: BoardId, bbbv: u16) {
+        self.boards.insert(board, BoardStats::new(bbbv));
+        self.bbbv = self
+            .boards
+            .values()
+            .fold(0, |bbbv, stats| bbbv.saturating_add(stats.bbbv));
+        self.outcome = None;
+    }
+
+    /// Stats of `board`
+    #[must_use]
+    pub fn board(&self, board: BoardId) -> Option<&BoardStats> {
+        self.boards.get(&board)
+    }
+
+    /// Iterate over the stats of every board, by id
+    pub fn boards(&self) -> impl Iterator<Item = (&BoardId, &BoardStats)> {
+        self.boards.iter()
+    }
+
+    /// Register a tile action on `board`, starting its timer
+    ///
+    /// Actions on boards already over are ignored.
+    pub fn add_click(
+        &mut self,
+        board: BoardId,
+        kind: ClickKind,
+        effective: bool,
+    ) {
+        let Some(stats) = self.boards.get_mut(&board) else {
+            return;
+        };
+        if stats.outcome.is_none() {
+            stats.started = true;
+            stats.clicks.add(kind, effective);
+            self.started = true;
+            self.clicks.add(kind, effective);
+        }
+    }
+
+    /// Advance the timers of the running boards and of the game
+    pub fn tick(&mut self, delta: Duration) {
+        if !self.is_running() {
+            return;
+        }
+        self.elapsed += delta;
+        for stats in self.boards.values_mut() {
+            if stats.is_running() {
+                stats.elapsed += delta;
+            }
+        }
+    }
+
+    /// Stop the timer of `board` with the given outcome, only the first one
+    /// is kept, and end the game once every board is over
+    pub fn finish(&mut self, board: BoardId, outcome: GameOutcome) {
+        let Some(stats) = self.boards.get_mut(&board) else {
+            return;
+        };
+        stats.outcome.get_or_insert(outcome);
+
+        if self.outcome.is_some()
+            || self.boards.values().any(|stats| stats.outcome.is_none())
+        {
+            return;
+        }
+        let won = self
+            .boards
+            .values()
+            .any(|stats| stats.outcome == Some(GameOutcome::Won));
+        self.outcome = Some(if won {
+            GameOutcome::Won
+        } else {
+            GameOutcome::Lost
+        });
+    }
+
+    /// Is the timer running
+    #[inline]
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.started && self.outcome.is_none()
+    }
+
+    /// Is the game played by a single player on a single board
+    #[inline]
+    #[must_use]
+    pub fn is_solo(&self) -> bool {
+        self.players <= 1 && self.boards.len() <= 1
+    }
+
+    /// 3BV solved per second, only meaningful once the game is won
+    #[must_use]
+    pub fn bbbv_per_second(&self) -> f32 {
+        bbbv_per_second(self.bbbv, self.elapsed)
+    }
+
+    /// 3BV per effective click, 1 when every click was necessary
+    #[must_use]
+    pub fn efficiency(&self) -> f32 {
+        efficiency(self.bbbv, &self.clicks)
+    }
+}
+
+fn bbbv_per_second(bbbv: u16, elapsed: Duration) -> f32 {
+    let seconds = elapsed.as_secs_f32();
+    if seconds > 0. {
+        bbbv as f32 / seconds
+    } else {
+        0.
+    }
+}
+
+fn efficiency(bbbv: u16, clicks: &ClickStats) -> f32 {
+    match clicks.effective() {
+        0 => 0.,
+        clicks => bbbv as f32 / clicks as f32,
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Player action bound in the [`InputMap`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    /// Uncover the tile under the mouse cursor, on release
+    Reveal,
+    /// Mark the tile under the mouse cursor
+    Flag,
+    /// Uncover the neighbors of the revealed number under the mouse cursor
+    Chord,
+    /// Start the game over
+    Restart,
+    /// Reserved for hints, no built-in handler
+    Hint,
+    /// Toggle the pause
+    Pause,
+    /// Reserved for undos, no built-in handler
+    Undo,
+    /// Leave the game, clearing the board
+    Clear,
+    /// Move keyboard cursors up
+    CursorUp,
+    /// Move keyboard cursors down
+    CursorDown,
+    /// Move keyboard cursors left
+    CursorLeft,
+    /// Move keyboard cursors right
+    CursorRight,
+    /// Uncover the tile under keyboard cursors, chording revealed numbers
+    CursorReveal,
+    /// Mark the tile under keyboard cursors
+    CursorFlag,
+}
+
+impl InputAction {
+    /// Every action, in display order
+    pub const ALL: [Self; 14] = [
+        Self::Reveal,
+        Self::Flag,
+        Self::Chord,
+        Self::Restart,
+        Self::Hint,
+        Self::Pause,
+        Self::Undo,
+        Self::Clear,
+        Self::CursorUp,
+        Self::CursorDown,
+        Self::CursorLeft,
+        Self::CursorRight,
+        Self::CursorReveal,
+        Self::CursorFlag,
+    ];
+}
+
+impl Display for InputAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reveal => write!(f, "reveal"),
+            Self::Flag => write!(f, "flag"),
+            Self::Chord => write!(f, "chord"),
+            Self::Restart => write!(f, "restart"),
+            Self::Hint => write!(f, "hint"),
+            Self::Pause => write!(f, "pause"),
+            Self::Undo => write!(f, "undo"),
+            Self::Clear => write!(f, "clear"),
+            Self::CursorUp => write!(f, "cursor up"),
+            Self::CursorDown => write!(f, "cursor down"),
+            Self::CursorLeft => write!(f, "cursor left"),
+            Self::CursorRight => write!(f, "cursor right"),
+            Self::CursorReveal => write!(f, "cursor reveal"),
+            Self::CursorFlag => write!(f, "cursor flag"),
+        }
+    }
+}
+
+/// Button triggering an action
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// Button of any connected gamepad
+    Gamepad(GamepadButtonType),
+}
+
+impl Display for InputBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key:?} key"),
+            Self::Mouse(button) => write!(f, "{button:?} mouse button"),
+            Self::Gamepad(button) => write!(f, "{button:?} gamepad button"),
+        }
+    }
+}
+
+/// Bindings of the player actions, persisted with the [`Preferences`]
+///
+/// [`Preferences`]: crate::resources::Preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Resource)]
+#[serde(default)]
+pub struct InputMap {
+    pub reveal: Vec<InputBinding>,
+    pub flag: Vec<InputBinding>,
+    pub chord: Vec<InputBinding>,
+    pub restart: Vec<InputBinding>,
+    pub hint: Vec<InputBinding>,
+    pub pause: Vec<InputBinding>,
+    pub undo: Vec<InputBinding>,
+    pub clear: Vec<InputBinding>,
+    pub cursor_up: Vec<InputBinding>,
+    pub cursor_down: Vec<InputBinding>,
+    pub cursor_left: Vec<InputBinding>,
+    pub cursor_right: Vec<InputBinding>,
+    pub cursor_reveal: Vec<InputBinding>,
+    pub cursor_flag: Vec<InputBinding>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use InputBinding::*;
+
+        Self {
+            reveal: vec![
+                Mouse(MouseButton::Left),
+                Gamepad(GamepadButtonType::South),
+            ],
+            flag: vec![
+                Mouse(MouseButton::Right),
+                Gamepad(GamepadButtonType::East),
+            ],
+            chord: vec![
+                Mouse(MouseButton::Middle),
+                Gamepad(GamepadButtonType::West),
+            ],
+            restart: vec![Key(KeyCode::G), Gamepad(GamepadButtonType::Select)],
+            hint: vec![Key(KeyCode::H)],
+            pause: vec![
+                Key(KeyCode::Escape),
+                Gamepad(GamepadButtonType::Start),
+            ],
+            undo: vec![Key(KeyCode::Z)],
+            clear: vec![Key(KeyCode::C)],
+            cursor_up: vec![Key(KeyCode::W)],
+            cursor_down: vec![Key(KeyCode::S)],
+            cursor_left: vec![Key(KeyCode::A)],
+            cursor_right: vec![Key(KeyCode::D)],
+            cursor_reveal: vec![Key(KeyCode::Space)],
+            cursor_flag: vec![Key(KeyCode::F)],
+        }
+    }
+}
+
+impl InputMap {
+    /// Bindings of `action`
+    #[must_use]
+    pub fn bindings(&self, action: InputAction) -> &[InputBinding] {
+        match action {
+            InputAction::Reveal => &self.reveal,
+            InputAction::Flag => &self.flag,
+            InputAction::Chord => &self.chord,
+            InputAction::Restart => &self.restart,
+            InputAction::Hint => &self.hint,
+            InputAction::Pause => &self.pause,
+            InputAction::Undo => &self.undo,
+            InputAction::Clear => &self.clear,
+            InputAction::CursorUp => &self.cursor_up,
+            InputAction::CursorDown => &self.cursor_down,
+            InputAction::CursorLeft => &self.cursor_left,
+            InputAction::CursorRight => &self.cursor_right,
+            InputAction::CursorReveal => &self.cursor_reveal,
+            InputAction::CursorFlag => &self.cursor_flag,
+        }
+    }
+
+    /// Mutable bindings of `action`
+    pub fn bindings_mut(
+        &mut self,
+        action: InputAction,
+    ) -> &mut Vec<InputBinding> {
+        match action {
+            InputAction::Reveal => &mut self.reveal,
+            InputAction::Flag => &mut self.flag,
+            InputAction::Chord => &mut self.chord,
+            InputAction::Restart => &mut self.restart,
+            InputAction::Hint => &mut self.hint,
+            InputAction::Pause => &mut self.pause,
+            InputAction::Undo => &mut self.undo,
+            InputAction::Clear => &mut self.clear,
+            InputAction::CursorUp => &mut self.cursor_up,
+            InputAction::CursorDown => &mut self.cursor_down,
+            InputAction::CursorLeft => &mut self.cursor_left,
+            InputAction::CursorRight => &mut self.cursor_right,
+            InputAction::CursorReveal => &mut self.cursor_reveal,
+            InputAction::CursorFlag => &mut self.cursor_flag,
+        }
+    }
+
+    /// Bind `action` to `binding` alone, removing it from the other actions
+    ///
+    /// An action losing its last binding gets the previous bindings of
+    /// `action` in exchange, so that no action is left unbound.
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) {
+        let previous =
+            std::mem::replace(self.bindings_mut(action), vec![binding]);
+
+        for other in InputAction::ALL.into_iter().filter(|a| *a != action) {
+            let bindings = self.bindings_mut(other);
+            if !bindings.contains(&binding) {
+                continue;
+            }
+            bindings.retain(|b| *b != binding);
+            if bindings.is_empty() {
+                bindings.extend(previous.iter().filter(|b| **b != binding));
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::resources::Replay;
+use bevy::log;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Number of entries kept per difficulty
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// High score, only accepted along with a replay reproducing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    /// Claimed time in milliseconds
+    pub time_ms: u32,
+    pub replay: Replay,
+}
+
+/// Error raised when an entry is refused
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LeaderboardError {
+    /// The replay doesn't clear the board
+    NotWon,
+    /// The replay clears the board at a different time than claimed
+    TimeMismatch { claimed: u32, replayed: u32 },
+    /// The board has no difficulty to be ranked in
+    Unranked,
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotWon => write!(f, "the replay doesn't clear the board"),
+            Self::TimeMismatch { claimed, replayed } => write!(
+                f,
+                "claimed {claimed}ms but the replay clears the board in \
+                 {replayed}ms"
+            ),
+            Self::Unranked => write!(f, "the board can't be ranked"),
+        }
+    }
+}
+
+impl Error for LeaderboardError {}
+
+/// Local high score table, grouped by difficulty
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+pub struct Leaderboard {
+    entries: BTreeMap<String, Vec<LeaderboardEntry>>,
+}
+
+impl Leaderboard {
+    /// Load the leaderboard stored at `path`, empty if there is none
+    ///
+    /// Entries whose replay doesn't reproduce their time are dropped.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut leaderboard: Self = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => default(),
+            Err(e) => return Err(e),
+        };
+
+        for entries in leaderboard.entries.values_mut() {
+            entries.retain(|entry| match Self::verify(entry) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::warn!("Dropped entry of {}: {e}", entry.name);
+                    false
+                }
+            });
+        }
+
+        Ok(leaderboard)
+    }
+
+    /// Store the leaderboard at `path`
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Verify the replay of `entry` and insert it, returning its rank
+    /// (starting at 0) or `None` if it didn't make the table
+    pub fn submit(
+        &mut self,
+        entry: LeaderboardEntry,
+    ) -> Result<Option<usize>, LeaderboardError> {
+        let difficulty = entry
+            .replay
+            .difficulty()
+            .ok_or(LeaderboardError::Unranked)?;
+        Self::verify(&entry)?;
+
+        let entries = self.entries.entry(difficulty).or_default();
+        let rank = entries.partition_point(|e| e.time_ms <= entry.time_ms);
+        if rank >= LEADERBOARD_SIZE {
+            return Ok(None);
+        }
+
+        entries.insert(rank, entry);
+        entries.truncate(LEADERBOARD_SIZE);
+
+        Ok(Some(rank))
+    }
+
+    /// Check that the replay of `entry` reproduces the claimed win
+    pub fn verify(entry: &LeaderboardEntry) -> Result<(), LeaderboardError> {
+        let replayed =
+            entry.replay.simulate().ok_or(LeaderboardError::NotWon)?;
+        if replayed != entry.time_ms {
+            return Err(LeaderboardError::TimeMismatch {
+                claimed: entry.time_ms,
+                replayed,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Entries of `difficulty`, fastest first
+    #[must_use]
+    pub fn entries(&self, difficulty: &str) -> &[LeaderboardEntry] {
+        self.entries.get(difficulty).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every difficulty with at least one entry
+    pub fn difficulties(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+}
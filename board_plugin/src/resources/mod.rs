@@ -1,9 +1,42 @@
 pub use board::*;
 pub use board_assets::*;
 pub use board_options::*;
+pub use board_players::*;
+pub use board_skin::*;
+pub use board_sounds::*;
+pub use board_state::*;
+pub use daily_challenge::*;
+pub use endless_board::*;
+pub use game_stats::*;
+pub use input_map::*;
+pub use leaderboard::*;
+pub use neighborhood::*;
+pub use palette::*;
+pub use player_stats::*;
+pub use preferences::*;
+pub use replay::*;
+pub use stats_history::*;
+pub use tile_mask::*;
 
 mod board;
 mod board_assets;
 mod board_options;
-pub(crate) mod tile;
+mod board_players;
+mod board_skin;
+mod board_sounds;
+mod board_state;
+pub(crate) mod chunk_map;
+mod daily_challenge;
+mod endless_board;
+mod game_stats;
+mod input_map;
+mod leaderboard;
+mod neighborhood;
+mod palette;
+mod player_stats;
+mod preferences;
+mod replay;
+mod stats_history;
+pub mod tile;
 pub(crate) mod tile_map;
+mod tile_mask;
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+/// Delta coordinates for all 8 square neighbors
+pub const SQUARE_COORDINATES: [(i8, i8); 8] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top Left
+    (-1, 1),
+    // Top
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+/// Delta coordinates for the 4 orthogonal neighbors
+pub const ORTHOGONAL_COORDINATES: [(i8, i8); 4] = [
+    // Bottom
+    (0, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top
+    (0, 1),
+];
+
+/// Delta coordinates for the 8 knight's move neighbors
+pub const KNIGHT_COORDINATES: [(i8, i8); 8] = [
+    (-1, -2),
+    (1, -2),
+    (-2, -1),
+    (2, -1),
+    (-2, 1),
+    (2, 1),
+    (-1, 2),
+    (1, 2),
+];
+
+/// Set of tiles considered adjacent when counting bombs and flood filling
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Neighborhood {
+    /// The 8 surrounding tiles
+    #[default]
+    Square,
+    /// The 4 orthogonal tiles
+    Orthogonal,
+    /// The 8 tiles a chess knight can reach
+    Knight,
+    /// Every tile in a square of the given radius
+    Radius(u8),
+    /// Custom list of delta coordinates
+    Custom(Vec<(i8, i8)>),
+}
+
+impl Neighborhood {
+    /// Delta coordinates of the neighborhood, never including `(0, 0)`
+    pub fn offsets(&self) -> Vec<(i8, i8)> {
+        match self {
+            Self::Square => SQUARE_COORDINATES.to_vec(),
+            Self::Orthogonal => ORTHOGONAL_COORDINATES.to_vec(),
+            Self::Knight => KNIGHT_COORDINATES.to_vec(),
+            Self::Radius(radius) => {
+                let radius = (*radius).min(i8::MAX as u8) as i8;
+                (-radius..=radius)
+                    .flat_map(|y| (-radius..=radius).map(move |x| (x, y)))
+                    .filter(|delta| *delta != (0, 0))
+                    .collect()
+            }
+            Self::Custom(offsets) => {
+                let mut offsets: Vec<_> = offsets
+                    .iter()
+                    .copied()
+                    .filter(|delta| *delta != (0, 0))
+                    .collect();
+                offsets.sort_unstable();
+                offsets.dedup();
+                offsets
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knight_offsets_are_the_knight_moves() {
+        let offsets = Neighborhood::Knight.offsets();
+
+        assert_eq!(offsets.len(), 8);
+        for (x, y) in offsets {
+            assert_eq!((x * y).abs(), 2);
+        }
+    }
+
+    #[test]
+    fn radius_offsets_cover_the_square_around() {
+        let offsets = Neighborhood::Radius(2).offsets();
+
+        assert_eq!(offsets.len(), 24);
+        assert!(!offsets.contains(&(0, 0)));
+        assert!(offsets.contains(&(-2, 2)));
+        assert!(!offsets.contains(&(3, 0)));
+        assert_eq!(Neighborhood::Radius(1).offsets().len(), 8);
+        assert!(Neighborhood::Radius(0).offsets().is_empty());
+    }
+
+    #[test]
+    fn custom_offsets_drop_the_center_and_duplicates() {
+        let neighborhood =
+            Neighborhood::Custom(vec![(0, 2), (0, 0), (1, 0), (0, 2)]);
+
+        assert_eq!(neighborhood.offsets(), [(0, 2), (1, 0)]);
+    }
+}
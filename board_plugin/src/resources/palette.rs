@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Number of bomb counter colors, one per count of the Moore neighborhood
+pub const PALETTE_SIZE: usize = 8;
+
+/// Bomb counter colors
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum CounterPalette {
+    /// Colors of the selected theme
+    #[default]
+    Theme,
+    /// Okabe & Ito palette, distinguishable with the common color blindnesses
+    OkabeIto,
+    /// Paul Tol's bright palette, color-blind safe
+    TolBright,
+}
+
+impl CounterPalette {
+    /// Every palette, in selection order
+    pub const ALL: [Self; 3] = [Self::Theme, Self::OkabeIto, Self::TolBright];
+
+    /// Colors of the counters 1 to 8, `None` to keep the theme colors
+    #[must_use]
+    pub fn colors(self) -> Option<[Color; PALETTE_SIZE]> {
+        let hex = match self {
+            Self::Theme => return None,
+            Self::OkabeIto => [
+                "56B4E9", "009E73", "E69F00", "0072B2", "D55E00", "CC79A7",
+                "F0E442", "FFFFFF",
+            ],
+            Self::TolBright => [
+                "4477AA", "228833", "EE6677", "66CCEE", "AA3377", "CCBB44",
+                "BBBBBB", "FFFFFF",
+            ],
+        };
+        Some(hex.map(|hex| Color::hex(hex).unwrap_or(Color::WHITE)))
+    }
+
+    /// Palette following this one in [`CounterPalette::ALL`]
+    #[must_use]
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl Display for CounterPalette {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Theme => write!(f, "theme"),
+            Self::OkabeIto => write!(f, "Okabe-Ito"),
+            Self::TolBright => write!(f, "Tol bright"),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::components::PlayerId;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Contribution of a single player to a game
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct PlayerContribution {
+    /// Safe tiles uncovered, including cascades
+    pub tiles_uncovered: u32,
+    /// Flags placed
+    pub flags_placed: u32,
+    /// Bombs uncovered
+    pub bombs_uncovered: u32,
+}
+
+/// Per player contribution stats of the current game
+#[derive(Debug, Clone, Default, Resource)]
+pub struct PlayerStats(HashMap<PlayerId, PlayerContribution>);
+
+impl PlayerStats {
+    /// Contribution of `player`, created on first access
+    pub fn player_mut(&mut self, player: PlayerId) -> &mut PlayerContribution {
+        self.0.entry(player).or_default()
+    }
+
+    /// Contribution of `player`
+    #[inline]
+    #[must_use]
+    pub fn get(&self, player: PlayerId) -> PlayerContribution {
+        self.0.get(&player).copied().unwrap_or_default()
+    }
+
+    /// Contributions sorted by player id
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerId, PlayerContribution)> {
+        let mut stats: Vec<_> = self.0.iter().map(|(k, v)| (*k, *v)).collect();
+        stats.sort_by_key(|(player, _)| *player);
+        stats.into_iter()
+    }
+}
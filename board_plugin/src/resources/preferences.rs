@@ -0,0 +1,108 @@
+use crate::resources::{CounterPalette, InputMap};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Volume step of the volume up/down keys
+pub const VOLUME_STEP: f32 = 0.1;
+/// Step of the UI scale up/down keys
+pub const UI_SCALE_STEP: f32 = 0.25;
+
+/// Sound settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioPreferences {
+    /// Volume of board sounds, between 0 and 1
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioPreferences {
+    fn default() -> Self {
+        Self {
+            volume: 0.5,
+            muted: false,
+        }
+    }
+}
+
+impl AudioPreferences {
+    /// Volume sounds should be played at, 0 when muted
+    #[inline]
+    #[must_use]
+    pub fn effective_volume(&self) -> f32 {
+        if self.muted {
+            0.
+        } else {
+            self.volume
+        }
+    }
+
+    /// Change the volume by `delta`, keeping it between 0 and 1
+    pub fn adjust_volume(&mut self, delta: f32) {
+        self.volume = (self.volume + delta).clamp(0., 1.);
+    }
+}
+
+/// Accessibility settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityPreferences {
+    /// Bomb counter colors
+    pub palette: CounterPalette,
+    /// Draw pips next to bomb counters so they don't rely on color alone
+    pub glyphs: bool,
+    /// Black and white board with bright counters
+    pub high_contrast: bool,
+    /// Scale factor of the UI text and nodes
+    pub ui_scale: f32,
+}
+
+impl Default for AccessibilityPreferences {
+    fn default() -> Self {
+        Self {
+            palette: CounterPalette::default(),
+            glyphs: false,
+            high_contrast: false,
+            ui_scale: 1.,
+        }
+    }
+}
+
+impl AccessibilityPreferences {
+    /// Change the UI scale by `delta`, keeping it between 0.5 and 3
+    pub fn adjust_ui_scale(&mut self, delta: f32) {
+        self.ui_scale = (self.ui_scale + delta).clamp(0.5, 3.);
+    }
+}
+
+/// User preferences, kept across launches
+///
+/// Missing fields fall back to their default so that older files still load.
+#[derive(
+    Debug, Default, Clone, PartialEq, Serialize, Deserialize, Resource,
+)]
+#[serde(default)]
+pub struct Preferences {
+    pub audio: AudioPreferences,
+    pub accessibility: AccessibilityPreferences,
+    pub bindings: InputMap,
+}
+
+impl Preferences {
+    /// Load the preferences stored at `path`, default if there are none
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Store the preferences at `path`
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
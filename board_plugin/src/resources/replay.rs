@@ -0,0 +1,129 @@
+use crate::components::Coordinates;
+use crate::resources::{
+    BoardOptions, BoardShape, BoardState, Neighborhood, Topology,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Tile action recorded in a replay
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ReplayActionKind {
+    Reveal,
+    Mark,
+}
+
+/// Single recorded tile action
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ReplayAction {
+    /// Milliseconds since the first action
+    pub time_ms: u32,
+    pub kind: ReplayActionKind,
+    pub coordinates: Coordinates,
+}
+
+/// Recorded single board game, enough to re-simulate it headlessly
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct Replay {
+    /// Generation options of the board
+    pub options: BoardOptions,
+    /// Seed of the bomb layout
+    pub seed: u64,
+    pub actions: Vec<ReplayAction>,
+}
+
+impl Replay {
+    /// Create an empty replay for a board generated with `options` and `seed`
+    #[must_use]
+    pub fn new(options: BoardOptions, seed: u64) -> Self {
+        Self {
+            options,
+            seed,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Record an action
+    pub fn push(
+        &mut self,
+        time_ms: u32,
+        kind: ReplayActionKind,
+        coordinates: Coordinates,
+    ) {
+        self.actions.push(ReplayAction {
+            time_ms,
+            kind,
+            coordinates,
+        });
+    }
+
+    /// Re-simulate the recorded actions against the seeded tile map,
+    /// returning the time of the winning action if the board gets cleared
+    #[must_use]
+    pub fn simulate(&self) -> Option<u32> {
+        let mut state = BoardState::new(self.options.tile_map(self.seed))
+            .with_question_marks(self.options.question_marks)
+            .with_no_flag(self.options.no_flag);
+
+        if self.options.safe_start {
+            if let Some(coords) = state.tile_map().safe_start() {
+                state.reveal(coords);
+            }
+        }
+
+        for action in &self.actions {
+            match action.kind {
+                ReplayActionKind::Reveal => {
+                    state.reveal(action.coordinates);
+                }
+                ReplayActionKind::Mark => {
+                    state.toggle_mark(action.coordinates);
+                }
+            }
+
+            if state.is_completed() {
+                return Some(action.time_ms);
+            }
+            if state.is_exploded() {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Difficulty label of the board, used to group leaderboard entries
+    ///
+    /// Classic boards are labeled by size and bomb count, other boards also
+    /// by their shape, topology, neighborhood and bombs per tile. Boards with
+    /// a custom mask or neighborhood can't be ranked and have no label.
+    #[must_use]
+    pub fn difficulty(&self) -> Option<String> {
+        let options = &self.options;
+        let (width, height) = options.map_size;
+        let mut label = format!("{width}x{height}/{}", options.bomb_count);
+
+        match options.shape {
+            BoardShape::Rectangle => (),
+            BoardShape::Circle => label.push_str(" circle"),
+            BoardShape::Heart => label.push_str(" heart"),
+            BoardShape::Mask(_) | BoardShape::Asset(_) => return None,
+        }
+        if options.topology == Topology::Toroidal {
+            label.push_str(" toroidal");
+        }
+        match options.neighborhood {
+            Neighborhood::Square => (),
+            Neighborhood::Orthogonal => label.push_str(" orthogonal"),
+            Neighborhood::Knight => label.push_str(" knight"),
+            Neighborhood::Radius(radius) => {
+                label.push_str(&format!(" radius {radius}"));
+            }
+            Neighborhood::Custom(_) => return None,
+        }
+        if options.max_bombs_per_tile > 1 {
+            label.push_str(&format!(" x{}", options.max_bombs_per_tile));
+        }
+
+        Some(label)
+    }
+}
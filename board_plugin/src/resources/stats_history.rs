@@ -0,0 +1,78 @@
+use crate::resources::{ClickStats, GameOutcome, GameStats, PlayStyle};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Stats accumulated over every game of a play style
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StyleStats {
+    pub played: u32,
+    pub won: u32,
+    pub clicks: ClickStats,
+    /// 3BV of the won games
+    pub bbbv: u32,
+    /// Effective clicks of the won games
+    pub won_effective_clicks: u32,
+    /// Time spent on the won games, in milliseconds
+    pub time_ms: u64,
+    /// Best efficiency of a won game
+    pub best_efficiency: f32,
+}
+
+impl StyleStats {
+    /// 3BV per effective click over the won games
+    #[must_use]
+    pub fn efficiency(&self) -> f32 {
+        match self.won_effective_clicks {
+            0 => 0.,
+            clicks => self.bbbv as f32 / clicks as f32,
+        }
+    }
+}
+
+/// Persisted stats, broken out by play style
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Resource)]
+pub struct StatsHistory {
+    styles: BTreeMap<PlayStyle, StyleStats>,
+}
+
+impl StatsHistory {
+    /// Load the stats stored at `path`, empty if there are none
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Store the stats at `path`
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Add a finished game to the stats of its style
+    pub fn record(&mut self, game: &GameStats) {
+        let stats = self.styles.entry(game.style).or_default();
+
+        stats.played += 1;
+        stats.clicks += game.clicks;
+        if game.outcome == Some(GameOutcome::Won) {
+            stats.won += 1;
+            stats.bbbv += u32::from(game.bbbv);
+            stats.won_effective_clicks += game.clicks.effective();
+            stats.time_ms += game.elapsed.as_millis() as u64;
+            stats.best_efficiency =
+                stats.best_efficiency.max(game.efficiency());
+        }
+    }
+
+    /// Stats of `style`
+    #[must_use]
+    pub fn get(&self, style: PlayStyle) -> StyleStats {
+        self.styles.get(&style).cloned().unwrap_or_default()
+    }
+}
@@ -1,30 +1,68 @@
 #[cfg(feature = "debug")]
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Tile {
-    Bomb,
+    /// Tile holding one or more bombs
+    Bomb(u8),
     BombNeighbor(u8),
     Empty,
+    /// Cell outside of the board shape
+    Void,
+}
+
+/// Player mark on a covered tile
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize,
+)]
+pub enum TileMark {
+    /// Unmarked
+    #[default]
+    None,
+    /// Flags, one per suspected bomb
+    Flag(u8),
+    /// Unsure, doesn't block reveals
+    Question,
+}
+
+impl TileMark {
+    /// Number of flags of the mark
+    #[inline]
+    #[must_use]
+    pub const fn flags(&self) -> u8 {
+        match self {
+            Self::Flag(v) => *v,
+            Self::None | Self::Question => 0,
+        }
+    }
 }
 
 impl Tile {
     /// check if tile is a bomb
     pub const fn is_bomb(&self) -> bool {
-        matches!(self, Self::Bomb)
+        matches!(self, Self::Bomb(_))
+    }
+
+    /// check if tile is outside of the board shape
+    pub const fn is_void(&self) -> bool {
+        matches!(self, Self::Void)
     }
 
     #[cfg(feature = "debug")]
     pub fn console_output(&self) -> String {
         let output = match self {
-            Tile::Bomb => "*".bright_red(),
+            Tile::Bomb(1) => "*".bright_red(),
+            Tile::Bomb(v) => v.to_string().bright_red(),
             Tile::BombNeighbor(v) => match v {
                 1 => "1".cyan(),
                 2 => "2".green(),
                 3 => "3".yellow(),
+                10.. => "+".red(),
                 _ => v.to_string().red(),
             },
             Tile::Empty => " ".normal(),
+            Tile::Void => "#".bright_black(),
         };
 
         output.to_string()
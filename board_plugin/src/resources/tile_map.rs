@@ -1,33 +1,20 @@
 use crate::components::Coordinates;
 use crate::resources::tile::Tile;
-use rand::{thread_rng, Rng};
+use crate::resources::{Neighborhood, TileMask, Topology, SQUARE_COORDINATES};
+use bevy::utils::HashSet;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::ops::{Deref, DerefMut};
 
-/// Delta coordinates for all 8 square neighbors
-const SQUARE_COORDINATES: [(i8, i8); 8] = [
-    // Bottom left
-    (-1, -1),
-    // Bottom
-    (0, -1),
-    // Bottom right
-    (1, -1),
-    // Left
-    (-1, 0),
-    // Right
-    (1, 0),
-    // Top Left
-    (-1, 1),
-    // Top
-    (0, 1),
-    // Top right
-    (1, 1),
-];
-
 #[derive(Debug, Clone)]
 pub struct TileMap {
     bomb_count: u16,
+    bomb_tile_count: u16,
+    max_bombs_per_tile: u8,
     width: u16,
     height: u16,
+    topology: Topology,
+    neighbors: Vec<(i8, i8)>,
     map: Vec<Vec<Tile>>,
 }
 
@@ -37,41 +24,138 @@ impl TileMap {
     #[must_use]
     pub fn empty(width: u16, height: u16) -> Self {
         let map = (0..height)
-            .into_iter()
-            .map(|_| (0..width).into_iter().map(|_| Tile::Empty).collect())
+            .map(|_| (0..width).map(|_| Tile::Empty).collect())
             .collect();
 
         Self {
             bomb_count: 0,
+            bomb_tile_count: 0,
+            max_bombs_per_tile: 1,
             width,
             height,
+            topology: Topology::Bounded,
+            neighbors: SQUARE_COORDINATES.to_vec(),
             map,
         }
     }
 
-    /// Spawn `bomb_count` bombs and randomly place them across the map.
-    pub fn set_bombs(&mut self, bomb_count: u16) {
+    /// Set the edge topology used for neighbor lookups
+    #[inline]
+    #[must_use]
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Set the neighborhood used for bomb counts and adjacency
+    #[inline]
+    #[must_use]
+    pub fn with_neighborhood(mut self, neighborhood: &Neighborhood) -> Self {
+        self.neighbors = neighborhood.offsets();
+        self
+    }
+
+    /// Turn every tile outside of `mask` into a void tile
+    pub fn apply_mask(&mut self, mask: &TileMask) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !mask.contains(Coordinates { x, y }) {
+                    self[y as usize][x as usize] = Tile::Void;
+                }
+            }
+        }
+    }
+
+    /// Set the maximum number of bombs a single tile can hold
+    #[inline]
+    #[must_use]
+    pub fn with_max_bombs_per_tile(mut self, max: u8) -> Self {
+        self.max_bombs_per_tile = max.max(1);
+        self
+    }
+
+    /// Spawn `bomb_count` bombs and randomly place them across the map, with
+    /// a layout derived from `seed` and identical on every platform.
+    ///
+    /// Tiles hold up to `max_bombs_per_tile` bombs each and the count is
+    /// capped to the capacity of the available tiles.
+    pub fn set_bombs_seeded(&mut self, bomb_count: u16, seed: u64) {
+        self.set_bombs_with_rng(
+            bomb_count,
+            &mut ChaCha8Rng::seed_from_u64(seed),
+        );
+    }
+
+    /// Same as [`TileMap::set_bombs_seeded`] using `rng` to place the bombs
+    pub fn set_bombs_with_rng(&mut self, bomb_count: u16, rng: &mut impl Rng) {
+        let available = self
+            .iter()
+            .flatten()
+            .filter(|tile| **tile == Tile::Empty)
+            .count()
+            * self.max_bombs_per_tile as usize;
+        let available = available.min(u16::MAX as usize) as u16;
+        let bomb_count = bomb_count.min(available);
         self.bomb_count = bomb_count;
 
         let mut remaining_bombs = bomb_count;
-        let mut rng = thread_rng();
 
         // place bombs
         while remaining_bombs > 0 {
             let x = rng.gen_range(0..self.width) as usize;
             let y = rng.gen_range(0..self.height) as usize;
 
-            if self[y][x] == Tile::Empty {
-                self[y][x] = Tile::Bomb;
-                remaining_bombs -= 1;
-            }
+            let tile = match self[y][x] {
+                Tile::Empty => Tile::Bomb(1),
+                Tile::Bomb(v) if v < self.max_bombs_per_tile => {
+                    Tile::Bomb(v + 1)
+                }
+                _ => continue,
+            };
+            self[y][x] = tile;
+            remaining_bombs -= 1;
         }
 
+        self.set_bomb_neighbors();
+    }
+
+    /// Place one bomb on each of `coordinates`, for handmade test layouts
+    ///
+    /// Repeated coordinates stack their bombs on the same tile.
+    ///
+    /// # Panics
+    ///
+    /// Panics on coordinates outside of the map or on void tiles, and when a
+    /// tile would hold more than `max_bombs_per_tile` bombs.
+    #[cfg(test)]
+    pub fn set_bombs_at(&mut self, coordinates: &[Coordinates]) {
+        for coords in coordinates {
+            let (x, y) = (coords.x as usize, coords.y as usize);
+            let tile = match self.map.get(y).and_then(|line| line.get(x)) {
+                Some(Tile::Empty | Tile::BombNeighbor(_)) => Tile::Bomb(1),
+                Some(Tile::Bomb(v)) if *v < self.max_bombs_per_tile => {
+                    Tile::Bomb(v + 1)
+                }
+                Some(Tile::Bomb(_)) => panic!("Too many bombs on {coords}"),
+                _ => panic!("No tile to hold a bomb on {coords}"),
+            };
+            self.map[y][x] = tile;
+            self.bomb_count += 1;
+        }
+
+        self.set_bomb_neighbors();
+    }
+
+    /// Update the tile counts and the bomb neighbors after placing bombs
+    fn set_bomb_neighbors(&mut self) {
+        self.bomb_tile_count =
+            self.iter().flatten().filter(|tile| tile.is_bomb()).count() as u16;
+
         // place bomb neighbors
         for y in 0..self.height {
             for x in 0..self.width {
                 let coords = Coordinates { x, y };
-                if self.is_bomb_at(coords) {
+                if self.is_bomb_at(coords) || self.is_void_at(coords) {
                     continue;
                 }
                 let bomb_count = self.bomb_count_at(coords);
@@ -96,7 +180,30 @@ impl TileMap {
         self.map[coordinates.y as usize][coordinates.x as usize].is_bomb()
     }
 
-    /// Count the number of adjacent tiles that are bombs
+    /// Number of bombs held by the tile at `coordinates`
+    #[inline]
+    #[must_use]
+    pub fn bombs_at(&self, coordinates: Coordinates) -> u8 {
+        if coordinates.x >= self.width || coordinates.y >= self.height {
+            return 0;
+        };
+        match self.map[coordinates.y as usize][coordinates.x as usize] {
+            Tile::Bomb(v) => v,
+            _ => 0,
+        }
+    }
+
+    /// Check if the tile at `coordinates` is outside of the board shape
+    #[inline]
+    #[must_use]
+    pub fn is_void_at(&self, coordinates: Coordinates) -> bool {
+        if coordinates.x >= self.width || coordinates.y >= self.height {
+            return true;
+        };
+        self.map[coordinates.y as usize][coordinates.x as usize].is_void()
+    }
+
+    /// Count the total number of bombs in adjacent tiles
     #[inline]
     #[must_use]
     pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
@@ -105,20 +212,94 @@ impl TileMap {
         }
 
         self.safe_square_at(coordinates)
-            .filter(|coordinates| self.is_bomb_at(*coordinates))
-            .count() as u8
+            .map(|coordinates| self.bombs_at(coordinates))
+            .fold(0, u8::saturating_add)
+    }
+
+    /// First empty tile in row order, uncovered by safe starts
+    #[must_use]
+    pub fn safe_start(&self) -> Option<Coordinates> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coordinates { x, y }))
+            .find(|coords| {
+                self[coords.y as usize][coords.x as usize] == Tile::Empty
+            })
+    }
+
+    /// Minimum number of clicks needed to clear the map (3BV)
+    ///
+    /// Every opening counts once, as does every numbered tile outside of them.
+    #[must_use]
+    pub fn bbbv(&self) -> u16 {
+        let mut visited = HashSet::new();
+        let mut count = 0;
+
+        let coordinates = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coordinates { x, y }));
+
+        for coords in coordinates.clone() {
+            if self[coords.y as usize][coords.x as usize] != Tile::Empty
+                || visited.contains(&coords)
+            {
+                continue;
+            }
+            count += 1;
+
+            let mut stack = vec![coords];
+            while let Some(coords) = stack.pop() {
+                if self.is_void_at(coords) || !visited.insert(coords) {
+                    continue;
+                }
+                if self[coords.y as usize][coords.x as usize] == Tile::Empty {
+                    stack.extend(self.safe_square_at(coords));
+                }
+            }
+        }
+
+        let isolated = coordinates.filter(|coords| {
+            matches!(
+                self[coords.y as usize][coords.x as usize],
+                Tile::BombNeighbor(_)
+            ) && !visited.contains(coords)
+        });
+
+        count + isolated.count() as u16
     }
 
     /// Get an iterator of tiles adjacent to the one at `coordinates`
+    /// according to the map neighborhood
+    ///
+    /// With a [`Topology::Toroidal`] map, neighbors wrap around the edges and
+    /// each of them is only returned once, even when the map is too small
+    /// for the neighborhood and several offsets wrap onto the same tile.
     #[inline]
     pub fn safe_square_at(
         &self,
         coordinates: Coordinates,
-    ) -> impl Iterator<Item = Coordinates> {
-        SQUARE_COORDINATES
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        let size = (self.width, self.height);
+        let mut seen = Vec::new();
+
+        self.neighbors
             .iter()
             .copied()
-            .map(move |coords| coordinates + coords)
+            .map(move |coords| match self.topology {
+                Topology::Bounded => coordinates + coords,
+                Topology::Toroidal => coordinates.wrapping_add(coords, size),
+            })
+            .filter(move |coords| {
+                if *coords == coordinates {
+                    return false;
+                }
+                if self.topology == Topology::Bounded {
+                    return true;
+                }
+                if seen.contains(coords) {
+                    return false;
+                }
+                seen.push(*coords);
+                true
+            })
     }
 
     #[cfg(feature = "debug")]
@@ -166,12 +347,33 @@ impl TileMap {
         self.height
     }
 
+    /// Getter for `topology`
+    #[inline]
+    #[must_use]
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
     /// Getter for `bomb_count`
     #[inline]
     #[must_use]
     pub fn bomb_count(&self) -> u16 {
         self.bomb_count
     }
+
+    /// Getter for `bomb_tile_count`, the number of tiles holding bombs
+    #[inline]
+    #[must_use]
+    pub fn bomb_tile_count(&self) -> u16 {
+        self.bomb_tile_count
+    }
+
+    /// Getter for `max_bombs_per_tile`
+    #[inline]
+    #[must_use]
+    pub fn max_bombs_per_tile(&self) -> u8 {
+        self.max_bombs_per_tile
+    }
 }
 
 impl Deref for TileMap {
@@ -187,3 +389,70 @@ impl DerefMut for TileMap {
         &mut self.map
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_bombs_stack_up_to_the_tile_capacity() {
+        let mut tile_map = TileMap::empty(3, 1).with_max_bombs_per_tile(2);
+        let bomb = Coordinates::new(0, 0);
+
+        tile_map.set_bombs_at(&[bomb, bomb]);
+
+        assert_eq!(tile_map.bombs_at(bomb), 2);
+        assert_eq!(tile_map.bomb_count(), 2);
+        assert_eq!(tile_map[0][1], Tile::BombNeighbor(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many bombs")]
+    fn repeated_bombs_over_the_tile_capacity_are_rejected() {
+        let bomb = Coordinates::new(0, 0);
+
+        TileMap::empty(3, 1).set_bombs_at(&[bomb, bomb]);
+    }
+
+    /// Toroidal map of `width` by `height` tiles with a bomb on `bombs`
+    fn toroidal(width: u16, height: u16, bombs: &[(u16, u16)]) -> TileMap {
+        let mut tile_map =
+            TileMap::empty(width, height).with_topology(Topology::Toroidal);
+        let bombs: Vec<_> = bombs
+            .iter()
+            .map(|(x, y)| Coordinates::new(*x, *y))
+            .collect();
+        tile_map.set_bombs_at(&bombs);
+        tile_map
+    }
+
+    #[test]
+    fn toroidal_corners_count_the_bombs_across_the_edges() {
+        let tile_map = toroidal(4, 4, &[(0, 0)]);
+
+        for (x, y) in [(3, 0), (0, 3), (3, 3)] {
+            assert_eq!(tile_map.bomb_count_at(Coordinates::new(x, y)), 1);
+        }
+        assert_eq!(tile_map.bomb_count_at(Coordinates::new(2, 2)), 0);
+    }
+
+    #[test]
+    fn toroidal_single_tile_has_no_neighbors() {
+        let tile_map = toroidal(1, 1, &[]);
+        let coords = Coordinates::new(0, 0);
+
+        assert_eq!(tile_map.safe_square_at(coords).count(), 0);
+        assert_eq!(tile_map.bomb_count_at(coords), 0);
+    }
+
+    #[test]
+    fn toroidal_neighbors_wrapping_onto_the_same_tile_count_once() {
+        let tile_map = toroidal(2, 2, &[(0, 0)]);
+
+        for (x, y) in [(1, 0), (0, 1), (1, 1)] {
+            let coords = Coordinates::new(x, y);
+            assert_eq!(tile_map.safe_square_at(coords).count(), 3);
+            assert_eq!(tile_map.bomb_count_at(coords), 1);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use crate::components::Coordinates;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::log;
+use bevy::prelude::{Assets, Handle, Image};
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::render::texture::{CompressedImageFormats, ImageType};
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Board shape customization options
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub enum BoardShape {
+    /// Full `map_size` rectangle
+    #[default]
+    Rectangle,
+    /// Ellipse inscribed in `map_size`
+    Circle,
+    /// Heart inscribed in `map_size`
+    Heart,
+    /// Custom mask, overrides `map_size`
+    Mask(TileMask),
+    /// Mask asset, such as a `.mask` file, swapped for its mask when the
+    /// board is created
+    #[serde(skip)]
+    Asset(Handle<TileMask>),
+}
+
+impl BoardShape {
+    /// Build the mask of the shape for a given map size, if any
+    pub fn mask(&self, (width, height): (u16, u16)) -> Option<TileMask> {
+        match self {
+            Self::Rectangle => None,
+            Self::Circle => Some(TileMask::from_fn(width, height, |x, y| {
+                x * x + y * y <= 1.
+            })),
+            Self::Heart => Some(TileMask::from_fn(width, height, |x, y| {
+                // Implicit heart curve scaled to fit in [-1, 1]
+                let (x, y) = (x * 1.2, y * 1.2 + 0.15);
+                (x * x + y * y - 1.).powi(3) - x * x * y.powi(3) <= 0.
+            })),
+            Self::Mask(mask) => Some(mask.clone()),
+            Self::Asset(_) => None,
+        }
+    }
+
+    /// Swap an asset shape for the mask it loaded, falling back to the full
+    /// rectangle while it isn't loaded
+    pub fn resolve(&mut self, masks: &Assets<TileMask>) {
+        let Self::Asset(handle) = self else {
+            return;
+        };
+        *self = match masks.get(handle) {
+            Some(mask) => Self::Mask(mask.clone()),
+            None => {
+                log::warn!("Board mask not loaded, using a rectangle");
+                Self::Rectangle
+            }
+        };
+    }
+}
+
+/// Error raised when a tile mask can't be built
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TileMaskError {
+    /// The mask has no cells
+    Empty,
+    /// A text mask contains an unknown character
+    InvalidCharacter { character: char, line: usize },
+    /// An image mask couldn't be decoded
+    InvalidImage(String),
+    /// The cells don't match the mask size
+    InvalidSize { expected: usize, found: usize },
+}
+
+impl Display for TileMaskError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "tile mask is empty"),
+            Self::InvalidCharacter { character, line } => {
+                write!(f, "invalid mask character {character:?} on line {line}")
+            }
+            Self::InvalidImage(e) => write!(f, "invalid mask image: {e}"),
+            Self::InvalidSize { expected, found } => {
+                write!(f, "expected {expected} mask cells, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for TileMaskError {}
+
+/// Board mask where `false` cells are void
+///
+/// Cells are stored bottom row first, matching board coordinates.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Serialize, Deserialize, TypeUuid, TypePath,
+)]
+#[serde(try_from = "TileMaskCells")]
+#[uuid = "a4318970-5877-4b52-9e72-2545ec82336e"]
+pub struct TileMask {
+    width: u16,
+    height: u16,
+    cells: Vec<bool>,
+}
+
+/// Serialized [`TileMask`], validated before use
+#[derive(Deserialize)]
+struct TileMaskCells {
+    width: u16,
+    height: u16,
+    cells: Vec<bool>,
+}
+
+impl TryFrom<TileMaskCells> for TileMask {
+    type Error = TileMaskError;
+
+    fn try_from(mask: TileMaskCells) -> Result<Self, Self::Error> {
+        Self::from_cells(mask.width, mask.height, mask.cells)
+    }
+}
+
+impl TileMask {
+    /// Build a mask by sampling `f` at every cell center, with coordinates
+    /// normalized to `[-1, 1]`
+    pub fn from_fn(
+        width: u16,
+        height: u16,
+        f: impl Fn(f32, f32) -> bool,
+    ) -> Self {
+        let normalize =
+            |v: u16, size: u16| (v as f32 + 0.5) / size.max(1) as f32 * 2. - 1.;
+
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(normalize(x, width), normalize(y, height)))
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse a text mask, top line first
+    ///
+    /// `#`, `x`, `X` and `1` are board cells, `.`, `0` and spaces are void.
+    pub fn parse(text: &str) -> Result<Self, TileMaskError> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let height = lines.len();
+
+        let mut cells = vec![false; width * height];
+
+        for (row, line) in lines.iter().enumerate() {
+            let y = height - 1 - row;
+            for (x, character) in line.chars().enumerate() {
+                cells[y * width + x] = match character {
+                    '#' | 'x' | 'X' | '1' => true,
+                    '.' | '0' | ' ' => false,
+                    character => {
+                        return Err(TileMaskError::InvalidCharacter {
+                            character,
+                            line: row + 1,
+                        })
+                    }
+                };
+            }
+        }
+
+        Self::from_cells(width as u16, height as u16, cells)
+    }
+
+    /// Build a mask from an image, top row first
+    ///
+    /// Opaque and bright pixels are board cells, everything else is void.
+    pub fn from_image(image: &Image) -> Result<Self, TileMaskError> {
+        let size = image.size();
+        let (width, height) = (size.x as usize, size.y as usize);
+        let pixel_count = width * height;
+
+        if pixel_count == 0 {
+            return Err(TileMaskError::Empty);
+        }
+
+        let data = &image.data;
+        let stride = data.len() / pixel_count;
+        let is_cell = |pixel: &[u8]| match pixel {
+            [luma] => *luma >= 128,
+            [luma, alpha] => *luma >= 128 && *alpha >= 128,
+            [r, g, b, alpha] => (*r).max(*g).max(*b) >= 128 && *alpha >= 128,
+            _ => false,
+        };
+
+        if !matches!(stride, 1 | 2 | 4) {
+            return Err(TileMaskError::InvalidImage(format!(
+                "unsupported format {:?}",
+                image.texture_descriptor.format
+            )));
+        }
+
+        let mut cells = vec![false; pixel_count];
+
+        for (i, pixel) in data.chunks_exact(stride).enumerate() {
+            let (x, row) = (i % width, i / width);
+            let y = height - 1 - row;
+            cells[y * width + x] = is_cell(pixel);
+        }
+
+        Self::from_cells(width as u16, height as u16, cells)
+    }
+
+    fn from_cells(
+        width: u16,
+        height: u16,
+        cells: Vec<bool>,
+    ) -> Result<Self, TileMaskError> {
+        let expected = width as usize * height as usize;
+        if cells.len() != expected {
+            return Err(TileMaskError::InvalidSize {
+                expected,
+                found: cells.len(),
+            });
+        }
+        if !cells.contains(&true) {
+            return Err(TileMaskError::Empty);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Check if `coordinates` is a board cell
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, coordinates: Coordinates) -> bool {
+        let Coordinates { x, y } = coordinates;
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        self.cells[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Mask size (width, height)
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+}
+
+/// Asset loader for `.mask` text files and `.mask.png` images
+#[derive(Debug, Default)]
+pub struct TileMaskLoader;
+
+impl AssetLoader for TileMaskLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let is_image =
+                load_context.path().to_string_lossy().ends_with(".png");
+
+            let mask = if is_image {
+                let image = Image::from_buffer(
+                    bytes,
+                    ImageType::Extension("png"),
+                    CompressedImageFormats::NONE,
+                    false,
+                )
+                .map_err(|e| TileMaskError::InvalidImage(e.to_string()))?;
+                TileMask::from_image(&image)?
+            } else {
+                TileMask::parse(&String::from_utf8_lossy(bytes))?
+            };
+
+            load_context.set_default_asset(LoadedAsset::new(mask));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["mask", "mask.png"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_masks_start_with_the_top_line() {
+        let mask = TileMask::parse("#.\n.#").unwrap();
+
+        assert_eq!(mask.size(), (2, 2));
+        assert!(mask.contains(Coordinates::new(0, 1)));
+        assert!(mask.contains(Coordinates::new(1, 0)));
+        assert!(!mask.contains(Coordinates::new(0, 0)));
+        assert!(!mask.contains(Coordinates::new(2, 0)));
+    }
+
+    #[test]
+    fn unknown_characters_are_refused_with_their_line() {
+        assert_eq!(
+            TileMask::parse("##\n#?"),
+            Err(TileMaskError::InvalidCharacter {
+                character: '?',
+                line: 2
+            })
+        );
+    }
+
+    #[test]
+    fn masks_without_cells_are_refused() {
+        assert_eq!(TileMask::parse(""), Err(TileMaskError::Empty));
+        assert_eq!(TileMask::parse("..\n  "), Err(TileMaskError::Empty));
+        assert_eq!(
+            TileMask::from_cells(2, 1, vec![false, false]),
+            Err(TileMaskError::Empty)
+        );
+    }
+
+    #[test]
+    fn cells_must_match_the_mask_size() {
+        assert_eq!(
+            TileMask::from_cells(2, 2, vec![true; 3]),
+            Err(TileMaskError::InvalidSize {
+                expected: 4,
+                found: 3
+            })
+        );
+
+        let json = r#"{"width": 2, "height": 2, "cells": [true, true, true]}"#;
+        assert!(serde_json::from_str::<TileMask>(json).is_err());
+        let json = r#"{"width": 1, "height": 1, "cells": [true]}"#;
+        assert!(serde_json::from_str::<TileMask>(json).is_ok());
+    }
+}
@@ -0,0 +1,305 @@
+use crate::components::{BoardId, BoardSprite, PlayerId};
+use crate::events::BombExplosionEvent;
+use crate::resources::tile::Tile;
+use crate::resources::{BoardAssets, EndlessBoard, InputAction, RevealResult};
+use crate::ActionInput;
+use bevy::ecs::system::EntityCommands;
+use bevy::log;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::time::Duration;
+
+/// Number of chunks kept spawned around the visible area
+const CHUNK_MARGIN: i32 = 1;
+
+/// Reveal or flag the tile under the mouse cursor
+pub fn handle_input(
+    mut commands: Commands,
+    actions: ActionInput,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut board: ResMut<EndlessBoard>,
+    board_assets: Res<BoardAssets>,
+    mut bomb_explosion_event_writer: EventWriter<BombExplosionEvent>,
+) {
+    let reveal = actions.just_pressed(InputAction::Reveal);
+    let flag = actions.just_pressed(InputAction::Flag);
+    if !reveal && !flag {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        log::debug!("Window not found.");
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        log::debug!("Camera not found.");
+        return;
+    };
+    let Some(position) = window
+        .cursor_position()
+        .and_then(|p| camera.viewport_to_world_2d(camera_transform, p))
+    else {
+        return;
+    };
+    let tile = board.tile_at_position(position);
+
+    if reveal {
+        match board.reveal(tile) {
+            RevealResult::Safe(tiles) => {
+                for tile in tiles {
+                    if let Some(cover) = board.covers.remove(&tile) {
+                        commands.entity(cover).despawn_recursive();
+                    }
+                }
+            }
+            RevealResult::Exploded => {
+                log::info!("Boom! Score: {}", board.score());
+                if let Some(cover) = board.covers.remove(&tile) {
+                    commands.entity(cover).despawn_recursive();
+                }
+                bomb_explosion_event_writer.send(BombExplosionEvent {
+                    board: BoardId::default(),
+                    player: PlayerId::default(),
+                    coordinates: None,
+                    elapsed: Duration::ZERO,
+                });
+            }
+            RevealResult::Ignored => (),
+        }
+    }
+    if flag {
+        let Some(flagged) = board.try_toggle_flag(tile) else {
+            return;
+        };
+        let Some(cover) = board.covers.get(&tile).copied() else {
+            return;
+        };
+        if flagged {
+            commands.entity(cover).with_children(|parent| {
+                spawn_flag(parent, &board, &board_assets);
+            });
+        } else {
+            commands.entity(cover).despawn_descendants();
+        }
+    }
+}
+
+/// Uncover the rest of the cascades too large to fit in a single frame
+pub fn continue_cascades(
+    mut commands: Commands,
+    mut board: ResMut<EndlessBoard>,
+) {
+    if board.pending.is_empty() {
+        return;
+    }
+    for tile in board.continue_cascade() {
+        if let Some(cover) = board.covers.remove(&tile) {
+            commands.entity(cover).despawn_recursive();
+        }
+    }
+}
+
+/// Spawn chunks entering the camera view and despawn the ones leaving it
+pub fn update_chunks(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<
+        (&GlobalTransform, &OrthographicProjection),
+        With<Camera2d>,
+    >,
+    mut board: ResMut<EndlessBoard>,
+    board_assets: Res<BoardAssets>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let center = camera_transform.translation().truncate();
+    let half_view =
+        Vec2::new(window.width(), window.height()) * projection.scale / 2.;
+
+    let min = board
+        .chunk_map
+        .chunk_of(board.tile_at_position(center - half_view))
+        - IVec2::splat(CHUNK_MARGIN);
+    let max = board
+        .chunk_map
+        .chunk_of(board.tile_at_position(center + half_view))
+        + IVec2::splat(CHUNK_MARGIN);
+    let in_view =
+        |chunk: &IVec2| chunk.cmpge(min).all() && chunk.cmple(max).all();
+
+    let far_chunks: Vec<IVec2> = board
+        .chunk_entities
+        .keys()
+        .filter(|chunk| !in_view(chunk))
+        .copied()
+        .collect();
+    for chunk in far_chunks {
+        if let Some(entity) = board.chunk_entities.remove(&chunk) {
+            commands.entity(entity).despawn_recursive();
+        }
+        let tiles: Vec<IVec2> = board.chunk_map.chunk_tiles(chunk).collect();
+        for tile in tiles {
+            board.covers.remove(&tile);
+        }
+    }
+
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            let chunk = IVec2::new(x, y);
+            if board.chunk_entities.contains_key(&chunk) {
+                continue;
+            }
+            log::trace!("Spawning chunk {chunk}");
+
+            let parent = board.entity;
+            let entity =
+                spawn_chunk(&mut commands, &mut board, &board_assets, chunk);
+            commands.entity(parent).add_child(entity);
+            board.chunk_entities.insert(chunk, entity);
+        }
+    }
+}
+
+/// Spawn the tiles of `chunk`, covering the ones not revealed yet
+fn spawn_chunk(
+    commands: &mut Commands,
+    board: &mut EndlessBoard,
+    board_assets: &BoardAssets,
+    chunk: IVec2,
+) -> Entity {
+    let size = board.tile_size - board.tile_padding;
+    let tiles: Vec<(IVec2, Tile)> = board
+        .chunk_map
+        .chunk_tiles(chunk)
+        .map(|tile| (tile, board.chunk_map.tile_at(tile)))
+        .collect();
+    let mut covers = Vec::new();
+
+    let entity = commands
+        .spawn(SpatialBundle::default())
+        .insert(Name::new(format!("Chunk {chunk}")))
+        .with_children(|parent| {
+            for (tile, content) in tiles {
+                let position = board.tile_position(tile);
+                let transform = Transform::from_xyz(position.x, position.y, 1.);
+                let mut cmd = spawn_sprite(
+                    parent,
+                    board_assets,
+                    BoardSprite::Tile,
+                    size,
+                    transform,
+                );
+                cmd.insert(Name::new(format!("Tile {tile}")));
+
+                cmd.with_children(|parent| {
+                    let role = match content {
+                        Tile::Bomb(_) => {
+                            Some(BoardSprite::Bomb { exploded: false })
+                        }
+                        Tile::BombNeighbor(v) => Some(BoardSprite::Counter(v)),
+                        Tile::Empty | Tile::Void => None,
+                    };
+                    if let Some(role) = role {
+                        let transform = Transform::from_xyz(0., 0., 1.);
+                        spawn_sprite(
+                            parent,
+                            board_assets,
+                            role,
+                            size,
+                            transform,
+                        );
+                    }
+
+                    if board.revealed.contains(&tile) {
+                        return;
+                    }
+
+                    let transform = Transform::from_xyz(0., 0., 2.);
+                    let mut cover = spawn_sprite(
+                        parent,
+                        board_assets,
+                        BoardSprite::Cover,
+                        size,
+                        transform,
+                    );
+                    cover.insert(Name::new("Tile Cover"));
+
+                    if board.flagged.contains(&tile) {
+                        cover.with_children(|parent| {
+                            spawn_flag(parent, board, board_assets);
+                        });
+                    }
+                    covers.push((tile, cover.id()));
+                });
+            }
+        })
+        .id();
+
+    board.covers.extend(covers);
+
+    entity
+}
+
+fn spawn_flag(
+    parent: &mut ChildBuilder,
+    board: &EndlessBoard,
+    board_assets: &BoardAssets,
+) {
+    let role = BoardSprite::Flag {
+        tint: None,
+        wrong: false,
+    };
+    let transform = Transform::from_xyz(0., 0., 1.);
+    spawn_sprite(parent, board_assets, role, board.tile_size, transform)
+        .insert(Name::new("Flag"));
+}
+
+/// Spawn a sprite drawing `role`, from the skin when it has a cell for it
+/// and from the board materials otherwise
+///
+/// The role is inserted along so that theme switches restyle the sprite.
+fn spawn_sprite<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    board_assets: &BoardAssets,
+    role: BoardSprite,
+    size: f32,
+    transform: Transform,
+) -> EntityCommands<'w, 's, 'a> {
+    let skin_sprite = role
+        .skin_tile()
+        .and_then(|tile| board_assets.skin_sprite(tile, size));
+    let mut cmd = if let Some(sprite) = skin_sprite {
+        parent.spawn(SpriteSheetBundle {
+            transform,
+            ..sprite
+        })
+    } else if let Some(material) = board_assets.material(role) {
+        parent.spawn(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(size)),
+                color: material.color,
+                ..default()
+            },
+            texture: material.texture,
+            transform,
+            ..default()
+        })
+    } else {
+        // Only counters have no material, they are drawn as text
+        let (BoardSprite::Counter(count) | BoardSprite::Count(count)) = role
+        else {
+            unreachable!("{role:?} has a material");
+        };
+        parent.spawn(Text2dBundle {
+            transform,
+            ..board_assets.bomb_count_text_bundle(count, size)
+        })
+    };
+    cmd.insert(role);
+    cmd
+}
@@ -0,0 +1,62 @@
+use crate::events::BombExplosionEvent;
+use crate::resources::Board;
+use crate::{BoardId, BoardSprite, Bomb, Coordinates};
+use bevy::prelude::*;
+
+/// Show the mines of a board once one of them exploded, the exploded mine and
+/// the flags placed on safe tiles being highlighted
+pub fn reveal_mines(
+    mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+    boards: Query<(Entity, &BoardId, &Board)>,
+    bombs: Query<(&Coordinates, &Parent, &Children), With<Bomb>>,
+    children: Query<&Children>,
+    mut visibilities: Query<&mut Visibility>,
+    mut sprites: Query<&mut BoardSprite>,
+) {
+    for event in bomb_explosion_event_reader.iter() {
+        let Some((board_entity, _, board)) =
+            boards.iter().find(|(_, id, _)| **id == event.board)
+        else {
+            continue;
+        };
+
+        let bombs = bombs
+            .iter()
+            .filter(|(_, parent, _)| parent.get() == board_entity);
+        for (coords, _, tile_children) in bombs {
+            if let Some(cover) = board.covered_tiles.get(coords) {
+                if !board.marked_tiles.contains_key(coords) {
+                    if let Ok(mut visibility) = visibilities.get_mut(*cover) {
+                        *visibility = Visibility::Hidden;
+                    }
+                }
+                continue;
+            }
+
+            // Uncovered mines are the ones that blew up
+            for child in tile_children.iter() {
+                if let Ok(mut sprite) = sprites.get_mut(*child) {
+                    if *sprite == (BoardSprite::Bomb { exploded: false }) {
+                        *sprite = BoardSprite::Bomb { exploded: true };
+                    }
+                }
+            }
+        }
+
+        let wrong_flags = board
+            .marked_tiles
+            .keys()
+            .filter(|coords| !board.tile_map.is_bomb_at(**coords))
+            .filter_map(|coords| board.covered_tiles.get(coords))
+            .filter_map(|cover| children.get(*cover).ok());
+        for flags in wrong_flags {
+            for flag in flags.iter() {
+                if let Ok(mut sprite) = sprites.get_mut(*flag) {
+                    if let BoardSprite::Flag { tint, wrong: false } = *sprite {
+                        *sprite = BoardSprite::Flag { tint, wrong: true };
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::components::GhostTile;
+use crate::resources::{Board, BoardAssets};
+use bevy::prelude::*;
+
+/// Alpha applied to ghost tiles so they read as hints rather than tiles
+pub const GHOST_ALPHA: f32 = 0.35;
+
+/// Mirror the covered state of edge tiles onto their ghost tiles
+pub fn sync_ghost_tiles(
+    boards: Query<Ref<Board>>,
+    board_assets: Res<BoardAssets>,
+    mut ghosts: Query<(&GhostTile, &Parent, &mut Sprite, &mut Handle<Image>)>,
+) {
+    for (ghost, parent, mut sprite, mut texture) in ghosts.iter_mut() {
+        let Ok(board) = boards.get(parent.get()) else {
+            continue;
+        };
+        if !board.is_changed() && !board_assets.is_changed() {
+            continue;
+        }
+
+        let material = if board.covered_tiles.contains_key(&ghost.source) {
+            &board_assets.covered_tile_material
+        } else {
+            &board_assets.tile_material
+        };
+
+        sprite.color = material.color.with_a(GHOST_ALPHA);
+        if *texture != material.texture {
+            *texture = material.texture.clone();
+        }
+    }
+}
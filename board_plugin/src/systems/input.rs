@@ -1,14 +1,37 @@
+use crate::components::{
+    BoardCursor, BoardId, BoardInput, BoardSprite, MousePress, PlayerId,
+};
 use crate::events::{TileMarkEvent, TileTriggerEvent};
-use crate::Board;
-use bevy::input::mouse::MouseButtonInput;
+use crate::resources::InputAction;
+use crate::{ActionInput, Board};
 use bevy::log;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+/// Player cursors, with the mouse press they hold
+type PlayerCursors<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Parent,
+        &'static PlayerId,
+        &'static BoardInput,
+        Option<&'static MousePress>,
+    ),
+    With<BoardCursor>,
+>;
+
+/// Reveal the tile under mouse cursors when the reveal action is released,
+/// after being pressed on their board, chord and mark on press
+///
+/// Releasing the reveal action off the board cancels the reveal.
 pub fn handle_input(
+    mut commands: Commands,
+    actions: ActionInput,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    board: Res<Board>,
-    mut button_event_reader: EventReader<MouseButtonInput>,
+    boards: Query<(&BoardId, &Board)>,
+    cursors: PlayerCursors,
     mut tile_trigger_event_writer: EventWriter<TileTriggerEvent>,
     mut tile_mark_event_writer: EventWriter<TileMarkEvent>,
 ) {
@@ -17,27 +40,228 @@ pub fn handle_input(
         return;
     };
 
-    let events = button_event_reader
-        .iter()
-        .filter(|event| event.state.is_pressed());
+    for (entity, parent, player, input, press) in cursors.iter() {
+        if *input != BoardInput::Mouse {
+            continue;
+        }
+        let Ok((id, board)) = boards.get(parent.get()) else {
+            continue;
+        };
+        let coordinates = board.mouse_position(window);
+        let trigger = |coordinates| TileTriggerEvent {
+            board: *id,
+            player: *player,
+            coordinates,
+        };
 
-    for event in events {
-        log::trace!("Mouse button pressed: {:?}", event.button);
+        let mut pressing = press.is_some();
+        if actions.just_pressed(InputAction::Reveal) && coordinates.is_some() {
+            pressing = true;
+        }
+        if actions.just_released(InputAction::Reveal) && pressing {
+            pressing = false;
+            match coordinates {
+                Some(coordinates) => {
+                    log::info!("Trying to uncover tile on {}", coordinates);
+                    tile_trigger_event_writer.send(trigger(coordinates));
+                }
+                None => log::debug!("Reveal cancelled off the board"),
+            }
+        }
+        match (press.is_some(), pressing) {
+            (false, true) => {
+                commands.entity(entity).insert(MousePress);
+            }
+            (true, false) => {
+                commands.entity(entity).remove::<MousePress>();
+            }
+            _ => (),
+        }
 
-        let Some(coordinates) = board.mouse_position(window) else {
+        let Some(coordinates) = coordinates else {
             continue;
         };
+        // Triggering a revealed number chords it
+        if actions.just_pressed(InputAction::Chord)
+            && !board.covered_tiles.contains_key(&coordinates)
+        {
+            log::info!("Trying to chord tile on {}", coordinates);
+            tile_trigger_event_writer.send(trigger(coordinates));
+        }
+        if actions.just_pressed(InputAction::Flag) && !board.no_flag {
+            log::info!("Trying to mark tile on {}", coordinates);
+            tile_mark_event_writer.send(TileMarkEvent {
+                board: *id,
+                player: *player,
+                coordinates,
+            });
+        }
+    }
+}
+
+/// Show the covers held down by mouse cursors as pressed, along with the
+/// covered neighbors of a held down revealed number which would be chorded
+pub fn preview_pressed_tiles(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    boards: Query<&Board>,
+    cursors: Query<&Parent, (With<BoardCursor>, With<MousePress>)>,
+    mut covers: Query<&mut BoardSprite>,
+    mut pressed: Local<Vec<Entity>>,
+) {
+    let mut held = Vec::new();
+    if let Ok(window) = window_query.get_single() {
+        for parent in cursors.iter() {
+            let Ok(board) = boards.get(parent.get()) else {
+                continue;
+            };
+            let Some(coordinates) = board.mouse_position(window) else {
+                continue;
+            };
+
+            if board.covered_tiles.contains_key(&coordinates) {
+                held.extend(board.tile_to_uncover(&coordinates).copied());
+            } else {
+                held.extend(
+                    board
+                        .tile_map
+                        .safe_square_at(coordinates)
+                        .filter_map(|c| board.tile_to_uncover(&c))
+                        .copied(),
+                );
+            }
+        }
+    }
 
-        match event.button {
-            MouseButton::Left => {
-                log::info!("Trying to uncover tile on {}", coordinates);
-                tile_trigger_event_writer.send(TileTriggerEvent(coordinates));
+    for entity in pressed.iter().filter(|entity| !held.contains(entity)) {
+        if let Ok(mut role) = covers.get_mut(*entity) {
+            if *role == BoardSprite::PressedCover {
+                *role = BoardSprite::Cover;
             }
-            MouseButton::Right => {
-                log::info!("Trying to mark tile on {}", coordinates);
-                tile_mark_event_writer.send(TileMarkEvent(coordinates));
+        }
+    }
+    for entity in &held {
+        if let Ok(mut role) = covers.get_mut(*entity) {
+            if *role == BoardSprite::Cover {
+                *role = BoardSprite::PressedCover;
             }
-            _ => (),
         }
     }
+    *pressed = held;
+}
+
+/// Move keyboard cursors and trigger or mark the tile under them
+pub fn handle_keyboard_input(
+    actions: ActionInput,
+    boards: Query<(&BoardId, &Board)>,
+    mut cursors: Query<
+        (&Parent, &PlayerId, &mut BoardInput),
+        With<BoardCursor>,
+    >,
+    mut tile_trigger_event_writer: EventWriter<TileTriggerEvent>,
+    mut tile_mark_event_writer: EventWriter<TileMarkEvent>,
+) {
+    for (parent, player, mut input) in cursors.iter_mut() {
+        let BoardInput::Keyboard(mut cursor) = *input else {
+            continue;
+        };
+        let Ok((id, board)) = boards.get(parent.get()) else {
+            continue;
+        };
+
+        let (width, height) = (board.tile_map.width(), board.tile_map.height());
+        let position = &mut cursor.position;
+
+        if actions.just_pressed(InputAction::CursorUp) {
+            position.y = (position.y + 1).min(height.saturating_sub(1));
+        }
+        if actions.just_pressed(InputAction::CursorDown) {
+            position.y = position.y.saturating_sub(1);
+        }
+        if actions.just_pressed(InputAction::CursorRight) {
+            position.x = (position.x + 1).min(width.saturating_sub(1));
+        }
+        if actions.just_pressed(InputAction::CursorLeft) {
+            position.x = position.x.saturating_sub(1);
+        }
+
+        // Only write back on moves to keep change detection meaningful
+        if *input != BoardInput::Keyboard(cursor) {
+            *input = BoardInput::Keyboard(cursor);
+        }
+
+        let coordinates = cursor.position;
+
+        if actions.just_pressed(InputAction::CursorReveal) {
+            log::info!("Trying to uncover tile on {}", coordinates);
+            tile_trigger_event_writer.send(TileTriggerEvent {
+                board: *id,
+                player: *player,
+                coordinates,
+            });
+        }
+        if actions.just_pressed(InputAction::CursorFlag) && !board.no_flag {
+            log::info!("Trying to mark tile on {}", coordinates);
+            tile_mark_event_writer.send(TileMarkEvent {
+                board: *id,
+                player: *player,
+                coordinates,
+            });
+        }
+    }
+}
+
+/// Cursors whose input state changed, along with their sprite transform
+type ChangedCursors<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Parent, &'static BoardInput, &'static mut Transform),
+    (With<BoardCursor>, Changed<BoardInput>),
+>;
+
+/// Move keyboard cursor sprites over their selected tile
+pub fn update_cursors(boards: Query<&Board>, mut cursors: ChangedCursors) {
+    for (parent, input, mut transform) in cursors.iter_mut() {
+        let BoardInput::Keyboard(cursor) = input else {
+            continue;
+        };
+        let Ok(board) = boards.get(parent.get()) else {
+            continue;
+        };
+        let size = board.tile_size;
+
+        transform.translation.x = cursor.position.x as f32 * size + size / 2.;
+        transform.translation.y = cursor.position.y as f32 * size + size / 2.;
+    }
+}
+
+/// Move mouse cursor sprites over the hovered tile, hiding them off the board
+pub fn update_mouse_cursors(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    boards: Query<&Board>,
+    mut cursors: Query<
+        (&Parent, &BoardInput, &mut Transform, &mut Visibility),
+        With<BoardCursor>,
+    >,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    for (parent, input, mut transform, mut visibility) in cursors.iter_mut() {
+        if *input != BoardInput::Mouse {
+            continue;
+        }
+        let Ok(board) = boards.get(parent.get()) else {
+            continue;
+        };
+        let size = board.tile_size;
+
+        let Some(coordinates) = board.mouse_position(window) else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        visibility.set_if_neq(Visibility::Visible);
+        transform.translation.x = coordinates.x as f32 * size + size / 2.;
+        transform.translation.y = coordinates.y as f32 * size + size / 2.;
+    }
 }
@@ -1,44 +1,167 @@
-use bevy::{log, prelude::*};
+use bevy::{log, prelude::*, utils::HashSet};
 
 use crate::{
-    events::TileMarkEvent,
-    resources::{Board, BoardAssets},
+    animation::{AnimationOptions, FlagDrop},
+    components::{BoardId, BoardSprite},
+    events::{TileMarkChangedEvent, TileMarkEvent},
+    resources::{
+        tile::TileMark, Board, BoardAssets, BoardPlayers, ClickKind, GameMode,
+        GameStats, PlayerStats, SkinTile,
+    },
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn mark_tiles(
     mut commands: Commands,
-    mut board: ResMut<Board>,
+    mut boards: Query<(&BoardId, &mut Board)>,
     board_assets: Res<BoardAssets>,
+    board_players: Option<Res<BoardPlayers>>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut game_stats: ResMut<GameStats>,
+    animations: Option<Res<AnimationOptions>>,
     mut tile_mark_event_reader: EventReader<TileMarkEvent>,
+    mut tile_mark_changed_event_writer: EventWriter<TileMarkChangedEvent>,
     query: Query<&Children>,
 ) {
-    for event in tile_mark_event_reader.iter() {
-        if let Some((entity, mark)) = board.try_toggle_mark(&event.0) {
-            if mark {
+    // The lowest player id wins simultaneous marks of the same tile
+    let mut events: Vec<_> = tile_mark_event_reader.iter().collect();
+    events.sort_by_key(|event| event.player);
+
+    let mut marked = HashSet::new();
+
+    for event in events {
+        if !marked.insert((event.board, event.coordinates)) {
+            log::debug!(
+                "Dropped conflicting mark of {} by {}",
+                event.coordinates,
+                event.player
+            );
+            // Marks lost to another player are not clicks of this one
+            continue;
+        }
+
+        let Some((_, mut board)) = boards
+            .iter_mut()
+            .find(|(id, board)| **id == event.board && !board.no_flag)
+        else {
+            continue;
+        };
+        let Some((entity, mark)) = board.try_toggle_mark(&event.coordinates)
+        else {
+            game_stats.add_click(event.board, ClickKind::Right, false);
+            continue;
+        };
+        game_stats.add_click(event.board, ClickKind::Right, true);
+        tile_mark_changed_event_writer.send(TileMarkChangedEvent {
+            board: event.board,
+            player: event.player,
+            coordinates: event.coordinates,
+            mark,
+            bombs_left: board.remaining_bombs(),
+        });
+        log::debug!("{} bombs left to flag", board.remaining_bombs());
+
+        if let Ok(children) = query.get(entity) {
+            for child in children.iter() {
+                commands.entity(*child).despawn_recursive();
+            }
+        } else if mark == TileMark::None {
+            log::error!("Failed to retrieve flag entity components.");
+            continue;
+        }
+
+        let size = board.tile_size;
+        let flags = match mark {
+            TileMark::None => continue,
+            TileMark::Question => {
+                let transform = Transform::from_xyz(0., 0., 1.);
                 commands.entity(entity).with_children(|parent| {
-                    parent
-                        .spawn(SpriteBundle {
-                            texture: board_assets.flag_material.texture.clone(),
+                    let mut question = match board_assets
+                        .skin_sprite(SkinTile::Question, size)
+                    {
+                        Some(sprite) => parent.spawn(SpriteSheetBundle {
+                            transform,
+                            ..sprite
+                        }),
+                        None => parent.spawn(SpriteBundle {
+                            texture: board_assets
+                                .question_material
+                                .texture
+                                .clone(),
                             sprite: Sprite {
-                                custom_size: Some(Vec2::splat(board.tile_size)),
-                                color: board_assets.flag_material.color,
+                                custom_size: Some(Vec2::splat(size)),
+                                color: board_assets.question_material.color,
                                 ..default()
                             },
-                            transform: Transform::from_xyz(0., 0., 1.),
+                            transform,
                             ..default()
-                        })
-                        .insert(Name::new("Flag"));
+                        }),
+                    };
+                    question
+                        .insert(Name::new("Question Mark"))
+                        .insert(BoardSprite::Question);
                 });
-            } else {
-                let Ok(children) = query.get(entity) else {
-                    log::error!("Failed to retrieve flag entity components.");
-                    continue;
-                };
-
-                for child in children.iter() {
-                    commands.entity(*child).despawn_recursive();
+                continue;
+            }
+            TileMark::Flag(flags) => flags,
+        };
+        player_stats.player_mut(event.player).flags_placed += 1;
+
+        // Flags are tinted with the color of their owner on shared boards
+        let tint = match &board_players {
+            Some(players) if players.mode == GameMode::Coop => {
+                Some(players.color(event.player).with_a(1.))
+            }
+            _ => None,
+        };
+        let role = BoardSprite::Flag { tint, wrong: false };
+
+        let flag_drop = animations
+            .as_ref()
+            .filter(|options| options.enabled)
+            .map(|options| FlagDrop::new(options, size));
+        let transform = flag_drop
+            .as_ref()
+            .map_or(Transform::from_xyz(0., 0., 1.), |drop| {
+                drop.start_transform(1.)
+            });
+
+        commands.entity(entity).with_children(|parent| {
+            let skin_sprite = board_assets.skin_sprite(SkinTile::Flag, size);
+            let mut flag = match (skin_sprite, board_assets.material(role)) {
+                (Some(mut sprite), _) => {
+                    sprite.sprite.color = tint.unwrap_or(Color::WHITE);
+                    parent.spawn(SpriteSheetBundle {
+                        transform,
+                        ..sprite
+                    })
                 }
+                (None, material) => {
+                    let material = material.unwrap_or_default();
+                    parent.spawn(SpriteBundle {
+                        texture: material.texture,
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(size)),
+                            color: material.color,
+                            ..default()
+                        },
+                        transform,
+                        ..default()
+                    })
+                }
+            };
+            flag.insert(Name::new("Flag")).insert(role);
+            if let Some(flag_drop) = flag_drop {
+                flag.insert(flag_drop);
             }
-        }
+
+            // Flag count on multi-bomb boards
+            if flags > 1 {
+                let mut text =
+                    board_assets.bomb_count_text_bundle(flags, size / 2.);
+                text.transform.translation.z = 2.;
+                parent.spawn(text).insert(BoardSprite::Count(flags));
+            }
+        });
     }
 }
@@ -1,3 +1,9 @@
+pub mod endless;
+pub mod game_over;
+pub mod ghost;
 pub mod input;
 pub mod mark;
+pub mod race;
+pub mod stats;
+pub mod theme;
 pub mod uncover;
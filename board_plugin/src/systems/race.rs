@@ -0,0 +1,22 @@
+use crate::components::BoardId;
+use crate::events::{BoardCompletedEvent, BombExplosionEvent};
+use crate::resources::Board;
+use bevy::log;
+use bevy::prelude::*;
+
+/// Report the boards finishing a race, when several boards are played side
+/// by side
+pub fn report_race_results(
+    boards: Query<&BoardId, With<Board>>,
+    mut board_completed_event_reader: EventReader<BoardCompletedEvent>,
+    mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+) {
+    let racing = boards.iter().count() > 1;
+
+    for event in board_completed_event_reader.iter().filter(|_| racing) {
+        log::info!("Board {} finished the race", event.board);
+    }
+    for event in bomb_explosion_event_reader.iter().filter(|_| racing) {
+        log::info!("Board {} is out of the race", event.board);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, TileMarkEvent, TileTriggerEvent,
+};
+use crate::resources::{GameOutcome, GameStats, Replay, ReplayActionKind};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Time the boards until they end, and the game until every one of them
+/// ended
+pub fn update_game_stats(
+    time: Res<Time>,
+    mut game_stats: ResMut<GameStats>,
+    mut board_completed_event_reader: EventReader<BoardCompletedEvent>,
+    mut bomb_explosion_event_reader: EventReader<BombExplosionEvent>,
+) {
+    game_stats.tick(time.delta());
+
+    for event in bomb_explosion_event_reader.iter() {
+        game_stats.finish(event.board, GameOutcome::Lost);
+    }
+    for event in board_completed_event_reader.iter() {
+        game_stats.finish(event.board, GameOutcome::Won);
+    }
+}
+
+/// Record the tile actions of single board games until they end
+pub fn record_replay(
+    game_stats: Res<GameStats>,
+    replay: Option<ResMut<Replay>>,
+    mut tile_trigger_event_reader: EventReader<TileTriggerEvent>,
+    mut tile_mark_event_reader: EventReader<TileMarkEvent>,
+) {
+    let Some(mut replay) = replay.filter(|_| game_stats.outcome.is_none())
+    else {
+        tile_trigger_event_reader.clear();
+        tile_mark_event_reader.clear();
+        return;
+    };
+    let time_ms = game_stats.elapsed.as_millis() as u32;
+
+    // Actions are recorded as the board resolves them, so that replays only
+    // hold the ones applied: marks first unless flags are disabled, as they
+    // win over reveals of the same tile, then the first reveal of every tile
+    // in player order
+    let no_flag = replay.options.no_flag;
+    let mut marked = HashSet::new();
+    for event in tile_mark_event_reader.iter().filter(|_| !no_flag) {
+        if marked.insert(event.coordinates) {
+            replay.push(time_ms, ReplayActionKind::Mark, event.coordinates);
+        }
+    }
+
+    let mut triggered = HashSet::new();
+    let mut triggers: Vec<_> = tile_trigger_event_reader.iter().collect();
+    triggers.sort_by_key(|event| event.player);
+    for event in triggers {
+        if !marked.contains(&event.coordinates)
+            && triggered.insert(event.coordinates)
+        {
+            replay.push(time_ms, ReplayActionKind::Reveal, event.coordinates);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::components::BoardSprite;
+use crate::resources::{BoardAssets, Preferences};
+use bevy::prelude::*;
+
+/// Board sprites with their role, drawn as plain or atlas sprites or texts
+type StyledSprites<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Ref<'static, BoardSprite>,
+        Option<&'static Sprite>,
+        Option<&'static TextureAtlasSprite>,
+        Option<&'static Text>,
+    ),
+>;
+
+/// Board counters with their role, drawn as plain or atlas sprites or texts
+type GlyphCounters<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Ref<'static, BoardSprite>,
+        Option<&'static Sprite>,
+        Option<&'static TextureAtlasSprite>,
+        Option<&'static Text>,
+        Option<&'static Children>,
+    ),
+>;
+
+/// Pip of a counter glyph, child of the counter it tells apart
+#[derive(Debug, Component)]
+pub struct CounterGlyph;
+
+/// Size of a board sprite, whether drawn as a sprite, from an atlas or as text
+fn sprite_size(
+    sprite: Option<&Sprite>,
+    atlas_sprite: Option<&TextureAtlasSprite>,
+    text: Option<&Text>,
+) -> f32 {
+    sprite
+        .and_then(|s| s.custom_size)
+        .or_else(|| atlas_sprite.and_then(|s| s.custom_size))
+        .map(|size| size.x)
+        .or_else(|| {
+            let section = text?.sections.first()?;
+            Some(section.style.font_size * section.value.len() as f32)
+        })
+        .unwrap_or_default()
+}
+
+/// Dice pips of the counters 1 to 8, on a 3x3 grid centered on `(0, 0)`
+fn glyph_pips(count: u8) -> &'static [(f32, f32)] {
+    match count {
+        1 => &[(0., 0.)],
+        2 => &[(-1., 1.), (1., -1.)],
+        3 => &[(-1., 1.), (0., 0.), (1., -1.)],
+        4 => &[(-1., 1.), (1., 1.), (-1., -1.), (1., -1.)],
+        5 => &[(-1., 1.), (1., 1.), (0., 0.), (-1., -1.), (1., -1.)],
+        6 => &[
+            (-1., 1.),
+            (1., 1.),
+            (-1., 0.),
+            (1., 0.),
+            (-1., -1.),
+            (1., -1.),
+        ],
+        7 => &[
+            (-1., 1.),
+            (1., 1.),
+            (-1., 0.),
+            (0., 0.),
+            (1., 0.),
+            (-1., -1.),
+            (1., -1.),
+        ],
+        8 => &[
+            (-1., 1.),
+            (0., 1.),
+            (1., 1.),
+            (-1., 0.),
+            (1., 0.),
+            (-1., -1.),
+            (0., -1.),
+            (1., -1.),
+        ],
+        _ => &[],
+    }
+}
+
+/// Rebuild the look of board sprites in place, every one of them when the
+/// board assets change and otherwise those whose role changed
+///
+/// Size, alpha, transform and visibility are kept so that running animations
+/// and hidden covers are left untouched.
+pub fn restyle_board_sprites(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    sprites: StyledSprites,
+) {
+    let restyle_all = board_assets.is_changed() && !board_assets.is_added();
+
+    for (entity, role, sprite, atlas_sprite, text) in sprites.iter() {
+        if !restyle_all && (!role.is_changed() || role.is_added()) {
+            continue;
+        }
+
+        let size = sprite_size(sprite, atlas_sprite, text);
+        let alpha = sprite
+            .map(|s| s.color.a())
+            .or_else(|| atlas_sprite.map(|s| s.color.a()))
+            .unwrap_or(1.);
+
+        let mut cmd = commands.entity(entity);
+
+        let skinned = role
+            .skin_tile()
+            .and_then(|tile| board_assets.skin_sprite(tile, size));
+        if let Some(bundle) = skinned {
+            let tint = match *role {
+                BoardSprite::Flag {
+                    tint: Some(tint), ..
+                } => tint,
+                _ => Color::WHITE,
+            };
+            cmd.remove::<(Sprite, Handle<Image>, Text)>().insert((
+                TextureAtlasSprite {
+                    color: tint.with_a(alpha),
+                    ..bundle.sprite
+                },
+                bundle.texture_atlas,
+            ));
+        } else if let Some(material) = board_assets.material(*role) {
+            cmd.remove::<(TextureAtlasSprite, Handle<TextureAtlas>, Text)>()
+                .insert((
+                    Sprite {
+                        color: material.color.with_a(alpha),
+                        custom_size: Some(Vec2::splat(size)),
+                        ..default()
+                    },
+                    material.texture,
+                ));
+        } else if let BoardSprite::Counter(count) | BoardSprite::Count(count) =
+            *role
+        {
+            let bundle = board_assets.bomb_count_text_bundle(count, size);
+            cmd.remove::<(
+                Sprite,
+                Handle<Image>,
+                TextureAtlasSprite,
+                Handle<TextureAtlas>,
+            )>()
+            .insert((
+                bundle.text,
+                bundle.text_anchor,
+                bundle.text_2d_bounds,
+                bundle.text_layout_info,
+            ));
+        }
+    }
+}
+
+/// Draw the counter glyphs, dice pips in the top right corner of bomb counters
+/// telling them apart without relying on their color
+///
+/// Glyphs are rebuilt when the preferences or board assets change, and added
+/// to new counters.
+pub fn sync_counter_glyphs(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    preferences: Option<Res<Preferences>>,
+    counters: GlyphCounters,
+    glyphs: Query<(), With<CounterGlyph>>,
+) {
+    let enabled = preferences.as_ref().is_some_and(|p| p.accessibility.glyphs);
+    let rebuild_all = board_assets.is_changed()
+        || preferences.as_ref().is_some_and(|p| p.is_changed());
+
+    for (entity, role, sprite, atlas_sprite, text, children) in counters.iter()
+    {
+        let BoardSprite::Counter(count) = *role else {
+            continue;
+        };
+        if !rebuild_all && !role.is_changed() {
+            continue;
+        }
+
+        for child in children.into_iter().flatten() {
+            if glyphs.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        if !enabled {
+            continue;
+        }
+
+        let size = sprite_size(sprite, atlas_sprite, text);
+        let color = board_assets.get_bomb_color(count);
+        let spacing = size / 12.;
+        let corner = Vec2::splat(size * 0.32);
+        commands.entity(entity).with_children(|parent| {
+            for (x, y) in glyph_pips(count) {
+                let position = corner + Vec2::new(*x, *y) * spacing;
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(size / 16.)),
+                            color,
+                            ..default()
+                        },
+                        transform: Transform::from_translation(
+                            position.extend(0.1),
+                        ),
+                        ..default()
+                    })
+                    .insert(Name::new("Counter Glyph"))
+                    .insert(CounterGlyph);
+            }
+        });
+    }
+}
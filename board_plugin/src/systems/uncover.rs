@@ -1,35 +1,155 @@
+use crate::animation::{AnimationOptions, CoverFade};
 use crate::events::{
-    BoardCompletedEvent, BombExplosionEvent, TileTriggerEvent,
+    BoardCompletedEvent, BombExplosionEvent, CascadeEvent, ChordEvent,
+    TileMarkEvent, TileTriggerEvent, TileUncoveredEvent,
+};
+use crate::resources::{tile::Tile, ClickKind, GameStats, PlayerStats};
+use crate::{
+    Board, BoardId, Bomb, BombNeighbor, Coordinates, PlayerId, RevealOrigin,
+    Uncover,
 };
-use crate::{Board, Bomb, BombNeighbor, Coordinates, Uncover};
 use bevy::log;
 use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
 
 pub fn trigger_event_handler(
     mut commands: Commands,
-    board: Res<Board>,
+    boards: Query<(&BoardId, &Board)>,
+    mut game_stats: ResMut<GameStats>,
     mut tile_trigger_event_reader: EventReader<TileTriggerEvent>,
+    mut tile_mark_event_reader: EventReader<TileMarkEvent>,
+    mut chord_event_writer: EventWriter<ChordEvent>,
 ) {
-    for TileTriggerEvent(coords) in tile_trigger_event_reader.iter() {
-        if let Some(entity) = board.tile_to_uncover(coords) {
-            commands.entity(*entity).insert(Uncover);
+    // Marks win over reveals of the same tile in the same frame, on boards
+    // where they are applied
+    let marked: HashSet<(BoardId, Coordinates)> = tile_mark_event_reader
+        .iter()
+        .filter(|event| {
+            boards
+                .iter()
+                .any(|(id, board)| *id == event.board && !board.no_flag)
+        })
+        .map(|event| (event.board, event.coordinates))
+        .collect();
+
+    // The lowest player id wins simultaneous reveals of the same tile
+    let mut events: Vec<_> = tile_trigger_event_reader.iter().collect();
+    events.sort_by_key(|event| event.player);
+
+    let mut triggered = HashSet::new();
+
+    for event in events {
+        let key = (event.board, event.coordinates);
+        if marked.contains(&key) || !triggered.insert(key) {
+            log::debug!(
+                "Dropped conflicting reveal of {} by {}",
+                event.coordinates,
+                event.player
+            );
+            // Only reveals actually applied count as clicks
+            continue;
+        }
+
+        let Some((_, board)) =
+            boards.iter().find(|(id, _)| **id == event.board)
+        else {
+            continue;
+        };
+
+        let origin = RevealOrigin(event.coordinates);
+
+        // Clicking a revealed number chords it
+        if !board.covered_tiles.contains_key(&event.coordinates) {
+            let tiles = board.chord_tiles(&event.coordinates);
+            game_stats.add_click(
+                event.board,
+                ClickKind::Chord,
+                tiles.is_some(),
+            );
+            if let Some(tiles) = &tiles {
+                chord_event_writer.send(ChordEvent {
+                    board: event.board,
+                    player: event.player,
+                    coordinates: event.coordinates,
+                    tiles: tiles.len(),
+                });
+            }
+
+            for entity in tiles.into_iter().flatten() {
+                commands
+                    .entity(entity)
+                    .insert((Uncover, event.player, origin));
+            }
+            continue;
+        }
+
+        let entity = board.tile_to_uncover(&event.coordinates);
+        game_stats.add_click(event.board, ClickKind::Left, entity.is_some());
+
+        if let Some(entity) = entity {
+            commands
+                .entity(*entity)
+                .insert((Uncover, event.player, origin));
         }
     }
 }
 
+/// Covers to uncover, with the player and the tile starting the reveal
+type UncoveredCovers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Parent,
+        Option<&'static PlayerId>,
+        Option<&'static RevealOrigin>,
+    ),
+    With<Uncover>,
+>;
+
+#[allow(clippy::too_many_arguments)]
 pub fn uncover_tiles(
     mut commands: Commands,
-    mut board: ResMut<Board>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
+    mut boards: Query<(&BoardId, &mut Board)>,
+    children: UncoveredCovers,
+    parents: Query<(
+        &Coordinates,
+        &Parent,
+        Option<&Bomb>,
+        Option<&BombNeighbor>,
+    )>,
+    mut player_stats: ResMut<PlayerStats>,
+    game_stats: Res<GameStats>,
+    animations: Option<Res<AnimationOptions>>,
+    mut tile_uncovered_event_writer: EventWriter<TileUncoveredEvent>,
     mut board_completed_event_writer: EventWriter<BoardCompletedEvent>,
     mut bomb_explosion_event_writer: EventWriter<BombExplosionEvent>,
 ) {
-    for (entity, parent) in children.iter() {
-        commands.entity(entity).despawn_recursive();
+    let animations = animations.filter(|options| options.enabled);
 
-        let Ok((coords, bomb, bomb_counter)) = parents.get(parent.get()) else {
+    for (entity, parent, player, origin) in children.iter() {
+        let Ok((coords, board_entity, bomb, bomb_counter)) =
+            parents.get(parent.get())
+        else {
             log::error!("Parent not found!");
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        // The cover fades out on its own, the tile is uncovered right away
+        match &animations {
+            Some(options) => {
+                let distance = origin.map_or(0., |o| o.distance(*coords));
+                commands
+                    .entity(entity)
+                    .remove::<Uncover>()
+                    .insert(CoverFade::new(options, distance));
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+
+        let Ok((id, mut board)) = boards.get_mut(board_entity.get()) else {
+            log::error!("Board not found!");
             continue;
         };
 
@@ -40,18 +160,91 @@ pub fn uncover_tiles(
             None => log::debug!("Tried to uncover an already uncovered tile"),
         };
 
+        let player = player.copied().unwrap_or_default();
+        let stats = player_stats.player_mut(player);
+
+        let tile = board
+            .tile_map
+            .get(coords.y as usize)
+            .and_then(|line| line.get(coords.x as usize))
+            .copied()
+            .unwrap_or(Tile::Empty);
+        let origin_coords = origin.map_or(*coords, |o| o.0);
+        tile_uncovered_event_writer.send(TileUncoveredEvent {
+            board: *id,
+            player,
+            coordinates: *coords,
+            entity: parent.get(),
+            tile,
+            origin: origin_coords,
+            cascade: origin_coords != *coords,
+        });
+
+        // Events carry the time spent on their board
+        let elapsed = game_stats
+            .board(*id)
+            .map_or(game_stats.elapsed, |b| b.elapsed);
+
         if board.is_completed() {
-            log::info!("Board completed");
-            board_completed_event_writer.send(BoardCompletedEvent);
+            log::info!("Board {} completed", id);
+            board_completed_event_writer.send(BoardCompletedEvent {
+                board: *id,
+                player,
+                elapsed,
+                bombs: board.tile_map.bomb_count(),
+            });
         }
 
         if bomb.is_some() {
-            log::info!("Boom!");
-            bomb_explosion_event_writer.send(BombExplosionEvent);
-        } else if bomb_counter.is_none() {
-            for entity in board.adjacent_covered_tiles(*coords) {
-                commands.entity(entity).insert(Uncover);
+            log::info!("Boom! (board {}, player {})", id, player);
+            stats.bombs_uncovered += 1;
+            bomb_explosion_event_writer.send(BombExplosionEvent {
+                board: *id,
+                player,
+                coordinates: Some(*coords),
+                elapsed,
+            });
+        } else {
+            stats.tiles_uncovered += 1;
+            if bomb_counter.is_none() {
+                // The cascade is credited to the player who started it
+                let origin = origin.copied().unwrap_or(RevealOrigin(*coords));
+                for entity in board.adjacent_covered_tiles(*coords) {
+                    commands.entity(entity).insert((Uncover, player, origin));
+                }
             }
         }
     }
 }
+
+/// Report cascades once they stopped spreading, tiles being uncovered a ring
+/// per frame
+pub fn report_cascades(
+    mut cascades: Local<HashMap<(BoardId, PlayerId, Coordinates), usize>>,
+    mut tile_uncovered_event_reader: EventReader<TileUncoveredEvent>,
+    mut cascade_event_writer: EventWriter<CascadeEvent>,
+) {
+    let mut spreading = HashSet::new();
+    for event in tile_uncovered_event_reader.iter().filter(|e| e.cascade) {
+        let key = (event.board, event.player, event.origin);
+        *cascades.entry(key).or_default() += 1;
+        spreading.insert(key);
+    }
+
+    let settled: Vec<_> = cascades
+        .keys()
+        .filter(|key| !spreading.contains(*key))
+        .copied()
+        .collect();
+    for key in settled {
+        let (board, player, origin) = key;
+        if let Some(tiles) = cascades.remove(&key) {
+            cascade_event_writer.send(CascadeEvent {
+                board,
+                player,
+                origin,
+                tiles,
+            });
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::harness::BoardHarness;
+use crate::components::Coordinates;
+use crate::events::CascadeEvent;
+
+#[test]
+fn reveals_are_announced_with_the_tile_content() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    let announcer = harness.record_announcements();
+
+    harness.reveal(1, 1);
+
+    assert_eq!(announcer.take(), ["Revealed (1, 1): 1"]);
+}
+
+#[test]
+fn cascades_are_announced_once_with_the_outcome() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    let announcer = harness.record_announcements();
+
+    harness.reveal(0, 0);
+
+    let tiles = harness.events::<CascadeEvent>()[0].tiles;
+    let messages = announcer.take();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0], "Revealed (0, 0): empty");
+    assert!(messages.contains(&format!("Cascade revealed {tiles} tiles")));
+    assert!(messages.iter().any(|message| {
+        message.starts_with("Board cleared with 1 mines")
+            && message.ends_with("game won")
+    }));
+}
+
+#[test]
+fn marks_are_announced() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    let announcer = harness.record_announcements();
+
+    harness.mark(2, 2);
+    harness.mark(2, 2);
+
+    assert_eq!(announcer.take(), ["Flagged (2, 2)", "Unmarked (2, 2)"]);
+}
+
+#[test]
+fn explosions_are_announced() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    let announcer = harness.record_announcements();
+
+    harness.reveal(2, 2);
+
+    let messages = announcer.take();
+    assert_eq!(messages[0], "Revealed (2, 2): mine");
+    assert!(messages.iter().any(|message| {
+        message.starts_with("Mine hit after") && message.ends_with("game lost")
+    }));
+}
+
+#[test]
+fn tiles_are_described_with_the_flags_around() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+
+    harness.mark(2, 2);
+    harness.reveal(1, 1);
+
+    let board = harness.board();
+    assert_eq!(
+        board.describe_tile(&Coordinates::new(1, 1)),
+        "(1, 1): 1, 1 flag around"
+    );
+    assert_eq!(board.describe_tile(&Coordinates::new(2, 2)), "(2, 2): flag");
+    assert_eq!(
+        board.describe_rows(),
+        [
+            "Row 2: covered, covered, flag",
+            "Row 1: covered, 1, covered",
+            "Row 0: covered, covered, covered",
+        ]
+    );
+}
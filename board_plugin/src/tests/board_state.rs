@@ -0,0 +1,29 @@
+use crate::components::Coordinates;
+use crate::resources::{tile_map::TileMap, BoardState, RevealOutcome};
+
+#[test]
+fn exploding_chord_keeps_the_tiles_it_uncovered() {
+    // Bomb in the top left corner next to the number chorded, the other
+    // one keeps the board from being completed by the chord
+    let mut tile_map = TileMap::empty(4, 2);
+    tile_map.set_bombs_at(&[Coordinates::new(0, 1), Coordinates::new(3, 1)]);
+    let mut state = BoardState::new(tile_map);
+    let number = Coordinates::new(1, 0);
+
+    assert!(matches!(state.reveal(number), RevealOutcome::Revealed(_)));
+    // Wrong flag, the chord goes through the bomb
+    state.toggle_mark(Coordinates::new(2, 1)).unwrap();
+
+    let RevealOutcome::Exploded { revealed, tile } = state.reveal(number)
+    else {
+        panic!("expected an explosion");
+    };
+
+    assert_eq!(tile.coordinates, Coordinates::new(0, 1));
+    let coordinates: Vec<_> = revealed.iter().map(|t| t.coordinates).collect();
+    assert!(coordinates.contains(&Coordinates::new(0, 0)));
+    assert!(coordinates.contains(&Coordinates::new(2, 0)));
+    for coords in coordinates {
+        assert!(!state.is_covered(coords));
+    }
+}
@@ -0,0 +1,62 @@
+use super::harness::{BoardHarness, BOARD};
+use crate::components::{BoardId, Coordinates};
+use crate::resources::{Board, BoardOptions, Replay};
+use crate::{RestartBoard, SpawnBoard};
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+
+const SPAWNED: BoardId = BoardId(1);
+
+/// Spawn a second board, without bombs
+fn spawn_board(harness: &mut BoardHarness) {
+    SpawnBoard {
+        id: SPAWNED,
+        options: BoardOptions {
+            map_size: (2, 2),
+            bomb_count: 0,
+            ..default()
+        },
+        players: Vec::new(),
+    }
+    .apply(&mut harness.app.world);
+}
+
+/// Entity of the board `id`, if any
+fn board_entity(harness: &mut BoardHarness, id: BoardId) -> Option<Entity> {
+    harness
+        .app
+        .world
+        .query_filtered::<(Entity, &BoardId), With<Board>>()
+        .iter(&harness.app.world)
+        .find(|(_, board)| **board == id)
+        .map(|(entity, _)| entity)
+}
+
+#[test]
+fn spawning_a_board_drops_the_replay() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    assert!(harness.app.world.contains_resource::<Replay>());
+
+    spawn_board(&mut harness);
+
+    assert!(board_entity(&mut harness, SPAWNED).is_some());
+    assert!(!harness.app.world.contains_resource::<Replay>());
+}
+
+#[test]
+fn restarting_a_board_keeps_the_others() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    spawn_board(&mut harness);
+    let spawned = board_entity(&mut harness, SPAWNED);
+
+    RestartBoard { board: BOARD }.apply(&mut harness.app.world);
+
+    assert_eq!(board_entity(&mut harness, SPAWNED), spawned);
+    let restarted = board_entity(&mut harness, BOARD).unwrap();
+    assert_ne!(restarted, harness.board);
+
+    // The stored options give back the same layout
+    let board = harness.app.world.get::<Board>(restarted).unwrap();
+    assert!(board.tile_map.is_bomb_at(Coordinates::new(2, 2)));
+    assert_eq!(board.covered_tiles.len(), 9);
+}
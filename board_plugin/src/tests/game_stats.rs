@@ -0,0 +1,63 @@
+use crate::components::BoardId;
+use crate::resources::{ClickKind, GameOutcome, GameStats, PlayStyle};
+use std::time::Duration;
+
+const SECOND: Duration = Duration::from_secs(1);
+
+/// Race of two players, each on a board of 3BV 10
+fn race() -> GameStats {
+    let mut stats = GameStats::new(PlayStyle::Flagging, 2);
+    stats.add_board(BoardId(0), 10);
+    stats.add_board(BoardId(1), 10);
+    stats
+}
+
+#[test]
+fn race_goes_on_until_every_board_ended() {
+    let mut stats = race();
+    stats.add_click(BoardId(0), ClickKind::Left, true);
+    stats.add_click(BoardId(1), ClickKind::Left, true);
+    stats.tick(SECOND);
+
+    stats.finish(BoardId(0), GameOutcome::Lost);
+    stats.tick(SECOND);
+
+    assert_eq!(stats.outcome, None);
+    assert!(stats.is_running());
+    assert_eq!(stats.board(BoardId(0)).unwrap().elapsed, SECOND);
+    assert_eq!(stats.board(BoardId(1)).unwrap().elapsed, 2 * SECOND);
+
+    stats.finish(BoardId(1), GameOutcome::Won);
+
+    assert_eq!(stats.outcome, Some(GameOutcome::Won));
+    assert_eq!(stats.elapsed, 2 * SECOND);
+}
+
+#[test]
+fn race_clicks_are_scored_per_board() {
+    let mut stats = race();
+
+    stats.add_click(BoardId(0), ClickKind::Left, true);
+    stats.add_click(BoardId(0), ClickKind::Right, true);
+    stats.add_click(BoardId(1), ClickKind::Left, true);
+    stats.finish(BoardId(1), GameOutcome::Lost);
+    // Ended boards don't count clicks anymore
+    stats.add_click(BoardId(1), ClickKind::Left, true);
+
+    assert_eq!(stats.board(BoardId(0)).unwrap().clicks.total(), 2);
+    assert_eq!(stats.board(BoardId(1)).unwrap().clicks.total(), 1);
+    assert_eq!(stats.clicks.total(), 3);
+    assert_eq!(stats.bbbv, 20);
+}
+
+#[test]
+fn only_single_player_games_are_solo() {
+    let mut solo = GameStats::new(PlayStyle::Flagging, 1);
+    solo.add_board(BoardId(0), 10);
+    let mut coop = GameStats::new(PlayStyle::Flagging, 2);
+    coop.add_board(BoardId(0), 10);
+
+    assert!(solo.is_solo());
+    assert!(!coop.is_solo());
+    assert!(!race().is_solo());
+}
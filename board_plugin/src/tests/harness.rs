@@ -0,0 +1,204 @@
+use crate::components::{BoardId, Coordinates, PlayerId, Uncover};
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, CascadeEvent, ChordEvent,
+    TileMarkChangedEvent, TileMarkEvent, TileTriggerEvent, TileUncoveredEvent,
+};
+use crate::resources::{
+    Board, BoardAssets, BoardOptions, BoardPosition, TileSize,
+};
+use crate::{AnnouncerPlugin, BoardAnnouncer, BoardPlugin, RecordingAnnouncer};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+
+/// Board driven by the harness
+pub const BOARD: BoardId = BoardId(0);
+/// Single player of the board
+pub const PLAYER: PlayerId = PlayerId(0);
+/// Tile size of the board, laid out from the origin
+pub const TILE_SIZE: f32 = 10.;
+/// Frames allowed for a reveal to settle
+const MAX_FRAMES: usize = 100;
+
+/// Events fired by the board systems since the harness started
+#[derive(Debug, Resource)]
+pub struct Recorded<E: Event>(pub Vec<E>);
+
+impl<E: Event> Default for Recorded<E> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+fn record<E: Event + Clone>(
+    mut event_reader: EventReader<E>,
+    mut recorded: ResMut<Recorded<E>>,
+) {
+    recorded.0.extend(event_reader.iter().cloned());
+}
+
+/// State of the harness app, boards run in [`TestState::Running`]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, States)]
+pub enum TestState {
+    #[default]
+    Menu,
+    Running,
+}
+
+/// Headless app running the [`BoardPlugin`] on a known layout
+///
+/// No window nor renderer is needed: the board is created on entering
+/// [`TestState::Running`], laid out from the origin, and driven with
+/// [`TileTriggerEvent`]s and [`TileMarkEvent`]s.
+pub struct BoardHarness {
+    pub app: App,
+    pub board: Entity,
+}
+
+impl BoardHarness {
+    /// Board of `width` by `height` tiles with a bomb on each of `bombs`
+    pub fn new(width: u16, height: u16, bombs: &[(u16, u16)]) -> Self {
+        let bombs: Vec<_> = bombs
+            .iter()
+            .map(|(x, y)| Coordinates::new(*x, *y))
+            .collect();
+        Self::with_options(BoardOptions {
+            map_size: (width, height),
+            bomb_count: bombs.len() as u16,
+            layout: Some(bombs),
+            ..default()
+        })
+    }
+
+    /// Board generated from `options`, laid out from the origin with fixed
+    /// size tiles
+    pub fn with_options(options: BoardOptions) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), InputPlugin))
+            .add_state::<TestState>()
+            .insert_resource(Self::board_assets())
+            .insert_resource(BoardOptions {
+                position: BoardPosition::Custom(Vec3::ZERO),
+                tile_size: TileSize::Fixed(TILE_SIZE),
+                ..options
+            })
+            .add_plugins(BoardPlugin {
+                running_state: TestState::Running,
+            });
+        Self::record::<TileUncoveredEvent>(&mut app);
+        Self::record::<CascadeEvent>(&mut app);
+        Self::record::<ChordEvent>(&mut app);
+        Self::record::<TileMarkChangedEvent>(&mut app);
+        Self::record::<BombExplosionEvent>(&mut app);
+        Self::record::<BoardCompletedEvent>(&mut app);
+
+        app.world
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::Running);
+        app.update();
+
+        let board = app
+            .world
+            .query_filtered::<Entity, With<Board>>()
+            .single(&app.world);
+
+        Self { app, board }
+    }
+
+    /// Add the [`AnnouncerPlugin`], returning the announcements recorder
+    pub fn record_announcements(&mut self) -> RecordingAnnouncer {
+        let recorder = RecordingAnnouncer::default();
+        self.app
+            .insert_resource(BoardAnnouncer::new(recorder.clone()))
+            .add_plugins(AnnouncerPlugin {
+                running_state: TestState::Running,
+            });
+        recorder
+    }
+
+    /// Plain assets, nothing is ever rendered
+    fn board_assets() -> BoardAssets {
+        BoardAssets {
+            label: "Test".to_string(),
+            board_material: default(),
+            tile_material: default(),
+            covered_tile_material: default(),
+            bomb_counter_font: default(),
+            bomb_counter_colors: BoardAssets::default_colors(),
+            flag_material: default(),
+            question_material: default(),
+            bomb_material: default(),
+            skin: None,
+        }
+    }
+
+    fn record<E: Event + Clone>(app: &mut App) {
+        app.init_resource::<Recorded<E>>()
+            .add_systems(Last, record::<E>);
+    }
+
+    /// Click the tile at (`x`, `y`) and wait for the reveal to settle
+    pub fn reveal(&mut self, x: u16, y: u16) {
+        self.send(TileTriggerEvent {
+            board: BOARD,
+            player: PLAYER,
+            coordinates: Coordinates::new(x, y),
+        });
+        self.settle();
+    }
+
+    /// Right click the tile at (`x`, `y`)
+    pub fn mark(&mut self, x: u16, y: u16) {
+        self.send(TileMarkEvent {
+            board: BOARD,
+            player: PLAYER,
+            coordinates: Coordinates::new(x, y),
+        });
+        self.settle();
+    }
+
+    /// Send `event` to the board systems, processed on the next frame
+    pub fn send<E: Event>(&mut self, event: E) {
+        self.app.world.resource_mut::<Events<E>>().send(event);
+    }
+
+    /// Step frames until no tile is left to uncover, cascades spreading a
+    /// ring per frame, then once more for the cascades to be reported
+    pub fn settle(&mut self) {
+        let mut pending = self.app.world.query_filtered::<(), With<Uncover>>();
+        for _ in 0..MAX_FRAMES {
+            self.app.update();
+            if pending.iter(&self.app.world).next().is_none() {
+                break;
+            }
+        }
+        self.app.update();
+    }
+
+    /// State of the board
+    pub fn board(&self) -> &Board {
+        self.app
+            .world
+            .get::<Board>(self.board)
+            .expect("board entity should exist")
+    }
+
+    /// Is the tile at (`x`, `y`) still covered
+    pub fn is_covered(&self, x: u16, y: u16) -> bool {
+        self.board()
+            .covered_tiles
+            .contains_key(&Coordinates::new(x, y))
+    }
+
+    /// Flags placed on the tile at (`x`, `y`)
+    pub fn flags(&self, x: u16, y: u16) -> Option<u8> {
+        self.board()
+            .marked_tiles
+            .get(&Coordinates::new(x, y))
+            .copied()
+    }
+
+    /// Events of type `E` fired so far
+    pub fn events<E: Event>(&self) -> &[E] {
+        &self.app.world.resource::<Recorded<E>>().0
+    }
+}
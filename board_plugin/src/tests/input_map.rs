@@ -0,0 +1,30 @@
+use crate::resources::{InputAction, InputBinding, InputMap};
+use bevy::prelude::*;
+
+#[test]
+fn rebinding_takes_the_binding_from_other_actions() {
+    let mut input_map = InputMap::default();
+    let binding = InputBinding::Gamepad(GamepadButtonType::South);
+
+    input_map.rebind(InputAction::Flag, binding);
+
+    assert_eq!(input_map.bindings(InputAction::Flag), [binding]);
+    assert_eq!(
+        input_map.bindings(InputAction::Reveal),
+        [InputBinding::Mouse(MouseButton::Left)]
+    );
+}
+
+#[test]
+fn rebinding_swaps_the_last_binding_of_an_action() {
+    let mut input_map = InputMap::default();
+    let clear = InputBinding::Key(KeyCode::C);
+
+    input_map.rebind(InputAction::Undo, clear);
+
+    assert_eq!(input_map.bindings(InputAction::Undo), [clear]);
+    assert_eq!(
+        input_map.bindings(InputAction::Clear),
+        [InputBinding::Key(KeyCode::Z)]
+    );
+}
@@ -0,0 +1,74 @@
+use super::harness::{BoardHarness, BOARD, PLAYER};
+use crate::components::Coordinates;
+use crate::events::{TileMarkChangedEvent, TileUncoveredEvent};
+use crate::resources::tile::TileMark;
+
+#[test]
+fn marking_flags_a_covered_tile() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+
+    harness.mark(1, 1);
+
+    assert_eq!(harness.flags(1, 1), Some(1));
+    assert_eq!(harness.board().remaining_bombs(), 0);
+
+    let changes = harness.events::<TileMarkChangedEvent>();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].board, BOARD);
+    assert_eq!(changes[0].player, PLAYER);
+    assert_eq!(changes[0].coordinates, Coordinates::new(1, 1));
+    assert_eq!(changes[0].mark, TileMark::Flag(1));
+    assert_eq!(changes[0].bombs_left, 0);
+}
+
+#[test]
+fn marking_a_flag_again_removes_it() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+
+    harness.mark(0, 0);
+    harness.mark(0, 0);
+
+    assert_eq!(harness.flags(0, 0), None);
+    assert_eq!(harness.board().remaining_bombs(), 1);
+
+    let changes = harness.events::<TileMarkChangedEvent>();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[1].mark, TileMark::None);
+    assert_eq!(changes[1].bombs_left, 1);
+}
+
+#[test]
+fn flagged_tiles_are_not_revealed() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+
+    harness.mark(1, 1);
+    harness.reveal(1, 1);
+
+    assert!(harness.is_covered(1, 1));
+    assert!(harness.events::<TileUncoveredEvent>().is_empty());
+}
+
+#[test]
+fn revealed_tiles_cannot_be_marked() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+
+    harness.reveal(0, 0);
+    harness.mark(0, 0);
+
+    assert_eq!(harness.flags(0, 0), None);
+    assert!(harness.events::<TileMarkChangedEvent>().is_empty());
+}
+
+#[test]
+fn flags_around_a_number_chord_its_neighbors() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+
+    harness.reveal(0, 0);
+    harness.mark(1, 1);
+    harness.reveal(0, 0);
+
+    assert!(!harness.is_covered(1, 0));
+    assert!(!harness.is_covered(0, 1));
+    assert!(harness.is_covered(1, 1));
+    assert_eq!(harness.board().covered_tiles.len(), 6);
+}
@@ -0,0 +1,11 @@
+mod announcer;
+mod board_state;
+mod commands;
+mod game_stats;
+mod harness;
+mod input_map;
+mod mark;
+mod net;
+mod plugin;
+mod replay;
+mod uncover;
@@ -0,0 +1,263 @@
+use crate::components::Coordinates;
+use crate::net::protocol::{
+    read_message, write_message, ClientMessage, GameMode, ServerMessage,
+    MAX_LINE, PROTOCOL_VERSION,
+};
+use crate::net::server::{self, ServerOptions};
+use crate::resources::tile_map::TileMap;
+use std::io::{BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+/// Seed of the layouts, any seed gives the same tests
+const SEED: u64 = 42;
+
+/// Run a single game on a free localhost port
+fn start_server(options: ServerOptions) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || server::serve(listener, options));
+    address
+}
+
+/// Options of a game without bombs nor safe start, cleared by any reveal
+fn options(players: u8, mode: GameMode) -> ServerOptions {
+    ServerOptions {
+        players,
+        mode,
+        map_size: (4, 4),
+        bomb_count: 0,
+        safe_start: false,
+        ..Default::default()
+    }
+}
+
+/// Raw line client, reading with a timeout so that a silent server fails
+/// the test instead of hanging it
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(address: SocketAddr) -> Self {
+        let stream = TcpStream::connect(address).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        Self {
+            writer: stream.try_clone().unwrap(),
+            reader: BufReader::new(stream),
+        }
+    }
+
+    /// Connect and join, returning the assigned player id
+    fn join(address: SocketAddr) -> (Self, u8) {
+        let mut client = Self::connect(address);
+        client.send(&ClientMessage::Join {
+            version: PROTOCOL_VERSION,
+            name: "Player".to_string(),
+        });
+        let Some(ServerMessage::Welcome { player, .. }) = client.receive()
+        else {
+            panic!("expected a welcome message");
+        };
+        (client, player)
+    }
+
+    fn send(&mut self, message: &ClientMessage) {
+        write_message(&mut self.writer, message).unwrap();
+    }
+
+    fn send_line(&mut self, line: &str) {
+        self.writer.write_all(line.as_bytes()).unwrap();
+        self.writer.write_all(b"\n").unwrap();
+    }
+
+    fn receive(&mut self) -> Option<ServerMessage> {
+        read_message(&mut self.reader).unwrap()
+    }
+
+    /// Check that the server closed the connection
+    fn is_closed(&mut self) -> bool {
+        !matches!(
+            read_message::<_, ServerMessage>(&mut self.reader),
+            Ok(Some(_))
+        )
+    }
+}
+
+fn reveal(x: u16, y: u16) -> ClientMessage {
+    ClientMessage::Reveal {
+        coordinates: Coordinates::new(x, y),
+    }
+}
+
+#[test]
+fn game_starts_once_every_player_joined() {
+    let address = start_server(options(2, GameMode::Race));
+
+    let (mut first, first_id) = Client::join(address);
+    let (mut second, second_id) = Client::join(address);
+
+    assert_eq!((first_id, second_id), (0, 1));
+    for client in [&mut first, &mut second] {
+        assert!(matches!(
+            client.receive(),
+            Some(ServerMessage::BoardSpec {
+                width: 4,
+                height: 4,
+                players: 2,
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn race_progress_is_sent_to_opponents() {
+    let address = start_server(options(2, GameMode::Race));
+    let (mut first, _) = Client::join(address);
+    let (mut second, _) = Client::join(address);
+    first.receive();
+    second.receive();
+
+    first.send(&reveal(0, 0));
+
+    assert!(matches!(
+        first.receive(),
+        Some(ServerMessage::Revealed { player: 0, .. })
+    ));
+    assert_eq!(
+        second.receive(),
+        Some(ServerMessage::Progress {
+            player: 0,
+            remaining: 0
+        })
+    );
+}
+
+#[test]
+fn malformed_message_keeps_the_connection() {
+    let address = start_server(options(1, GameMode::Race));
+    let (mut client, _) = Client::join(address);
+    client.receive();
+
+    client.send_line("{\"type\": \"Reveal\"");
+
+    assert!(matches!(
+        client.receive(),
+        Some(ServerMessage::Invalid { .. })
+    ));
+
+    client.send(&reveal(0, 0));
+
+    assert!(matches!(
+        client.receive(),
+        Some(ServerMessage::Revealed { player: 0, .. })
+    ));
+    assert_eq!(
+        client.receive(),
+        Some(ServerMessage::Finished {
+            winner: Some(0),
+            cleared: true
+        })
+    );
+}
+
+#[test]
+fn oversized_message_drops_the_client() {
+    let address = start_server(options(1, GameMode::Race));
+    let (mut client, _) = Client::join(address);
+    client.receive();
+
+    client.send_line(&"x".repeat(MAX_LINE as usize + 1));
+
+    assert!(client.is_closed());
+}
+
+#[test]
+fn first_message_must_be_a_join() {
+    let address = start_server(options(1, GameMode::Race));
+    let mut client = Client::connect(address);
+
+    client.send_line("hello");
+
+    assert!(matches!(
+        client.receive(),
+        Some(ServerMessage::Rejected { .. })
+    ));
+}
+
+#[test]
+fn race_explosions_are_hidden_from_opponents() {
+    let mut tile_map = TileMap::empty(4, 4);
+    tile_map.set_bombs_seeded(1, SEED);
+    let bomb = (0..4)
+        .flat_map(|y| (0..4).map(move |x| Coordinates::new(x, y)))
+        .find(|coords| tile_map.is_bomb_at(*coords))
+        .unwrap();
+    let address = start_server(ServerOptions {
+        bomb_count: 1,
+        seed: SEED,
+        ..options(2, GameMode::Race)
+    });
+    let (mut first, _) = Client::join(address);
+    let (mut second, _) = Client::join(address);
+    first.receive();
+    second.receive();
+
+    first.send(&reveal(bomb.x, bomb.y));
+
+    assert!(matches!(
+        first.receive(),
+        Some(ServerMessage::Exploded { player: 0, .. })
+    ));
+    assert!(matches!(
+        second.receive(),
+        Some(ServerMessage::Eliminated { player: 0, .. })
+    ));
+}
+
+#[test]
+fn failed_joins_do_not_take_a_slot() {
+    let address = start_server(options(1, GameMode::Race));
+    let mut stranger = Client::connect(address);
+    stranger.send(&ClientMessage::Join {
+        version: PROTOCOL_VERSION + 1,
+        name: "Stranger".to_string(),
+    });
+    assert!(matches!(
+        stranger.receive(),
+        Some(ServerMessage::Rejected { .. })
+    ));
+
+    let (mut client, player) = Client::join(address);
+
+    assert_eq!(player, 0);
+    assert!(matches!(
+        client.receive(),
+        Some(ServerMessage::BoardSpec { .. })
+    ));
+}
+
+#[test]
+fn players_leaving_before_the_start_free_their_slot() {
+    let address = start_server(options(2, GameMode::Race));
+    let (leaving, _) = Client::join(address);
+    drop(leaving);
+    // Leave the server the time to notice
+    thread::sleep(Duration::from_millis(200));
+
+    let (mut first, first_id) = Client::join(address);
+    let (mut second, second_id) = Client::join(address);
+
+    assert_eq!((first_id, second_id), (0, 1));
+    for client in [&mut first, &mut second] {
+        assert!(matches!(
+            client.receive(),
+            Some(ServerMessage::BoardSpec { .. })
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+use super::harness::{BoardHarness, TestState, BOARD, PLAYER};
+use crate::components::{Coordinates, Uncover};
+use crate::events::TileTriggerEvent;
+use crate::resources::Board;
+use crate::{BoardSet, PauseState};
+use bevy::prelude::*;
+
+#[test]
+fn paused_boards_ignore_actions() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    harness
+        .app
+        .world
+        .resource_mut::<NextState<PauseState>>()
+        .set(PauseState::Paused);
+    harness.app.update();
+
+    harness.reveal(0, 0);
+
+    assert!(harness.is_covered(0, 0));
+}
+
+#[test]
+fn leaving_the_running_state_removes_the_board() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+
+    harness
+        .app
+        .world
+        .resource_mut::<NextState<TestState>>()
+        .set(TestState::Menu);
+    harness.app.update();
+
+    let mut boards = harness.app.world.query_filtered::<(), With<Board>>();
+    assert_eq!(boards.iter(&harness.app.world).count(), 0);
+}
+
+/// Host app input, clicking the bottom left tile in [`BoardSet::Input`]
+fn click_corner(mut tile_trigger_event_writer: EventWriter<TileTriggerEvent>) {
+    tile_trigger_event_writer.send(TileTriggerEvent {
+        board: BOARD,
+        player: PLAYER,
+        coordinates: Coordinates::new(0, 0),
+    });
+}
+
+#[test]
+fn input_set_is_processed_within_the_frame() {
+    let mut harness = BoardHarness::new(3, 3, &[(2, 2)]);
+    let cover = harness.board().covered_tiles[&Coordinates::new(0, 0)];
+    harness
+        .app
+        .add_systems(Update, click_corner.in_set(BoardSet::Input));
+
+    harness.app.update();
+
+    assert!(harness.app.world.get::<Uncover>(cover).is_some());
+}
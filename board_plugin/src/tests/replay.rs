@@ -0,0 +1,185 @@
+use crate::components::Coordinates;
+use crate::resources::{
+    BoardOptions, BoardShape, Leaderboard, LeaderboardEntry, LeaderboardError,
+    Neighborhood, Replay, ReplayActionKind, TileMask, Topology,
+    LEADERBOARD_SIZE,
+};
+
+/// Seed of the layouts, any seed gives the same tests
+const SEED: u64 = 42;
+
+/// 3x3 board without bombs, cleared by any reveal
+fn empty_board() -> BoardOptions {
+    BoardOptions {
+        map_size: (3, 3),
+        bomb_count: 0,
+        ..Default::default()
+    }
+}
+
+/// Replay of `options` with `actions` as (time, kind, x, y)
+fn replay(
+    options: BoardOptions,
+    actions: &[(u32, ReplayActionKind, u16, u16)],
+) -> Replay {
+    let mut replay = Replay::new(options, SEED);
+    for (time_ms, kind, x, y) in actions {
+        replay.push(*time_ms, *kind, Coordinates::new(*x, *y));
+    }
+    replay
+}
+
+/// Winning replay of the empty board, claimed at `time_ms`
+fn entry(name: &str, time_ms: u32) -> LeaderboardEntry {
+    LeaderboardEntry {
+        name: name.to_string(),
+        time_ms,
+        replay: replay(
+            empty_board(),
+            &[(time_ms, ReplayActionKind::Reveal, 1, 1)],
+        ),
+    }
+}
+
+#[test]
+fn winning_replay_returns_the_time_of_the_last_action() {
+    let replay =
+        replay(empty_board(), &[(1200, ReplayActionKind::Reveal, 0, 0)]);
+
+    assert_eq!(replay.simulate(), Some(1200));
+}
+
+#[test]
+fn exploding_replay_is_not_won() {
+    let options = BoardOptions {
+        map_size: (4, 4),
+        bomb_count: 1,
+        ..Default::default()
+    };
+    let tile_map = options.tile_map(SEED);
+    let bomb = (0..4)
+        .flat_map(|y| (0..4).map(move |x| Coordinates::new(x, y)))
+        .find(|coords| tile_map.is_bomb_at(*coords))
+        .unwrap();
+
+    let replay =
+        replay(options, &[(500, ReplayActionKind::Reveal, bomb.x, bomb.y)]);
+
+    assert_eq!(replay.simulate(), None);
+}
+
+#[test]
+fn marks_are_ignored_without_flags() {
+    let actions = [
+        (100, ReplayActionKind::Mark, 1, 1),
+        (200, ReplayActionKind::Reveal, 1, 1),
+    ];
+
+    // The flag protects the tile from the reveal
+    assert_eq!(replay(empty_board(), &actions).simulate(), None);
+
+    let no_flag = BoardOptions {
+        no_flag: true,
+        ..empty_board()
+    };
+    assert_eq!(replay(no_flag, &actions).simulate(), Some(200));
+}
+
+#[test]
+fn verify_checks_the_claimed_time() {
+    assert_eq!(Leaderboard::verify(&entry("Ann", 900)), Ok(()));
+
+    let mut late = entry("Ann", 900);
+    late.time_ms = 800;
+    assert_eq!(
+        Leaderboard::verify(&late),
+        Err(LeaderboardError::TimeMismatch {
+            claimed: 800,
+            replayed: 900
+        })
+    );
+
+    let mut lost = entry("Ann", 900);
+    lost.replay.actions.clear();
+    assert_eq!(Leaderboard::verify(&lost), Err(LeaderboardError::NotWon));
+}
+
+#[test]
+fn leaderboard_keeps_the_fastest_first() {
+    let mut leaderboard = Leaderboard::default();
+
+    assert_eq!(leaderboard.submit(entry("Ann", 300)), Ok(Some(0)));
+    assert_eq!(leaderboard.submit(entry("Bob", 100)), Ok(Some(0)));
+    assert_eq!(leaderboard.submit(entry("Cid", 200)), Ok(Some(1)));
+    // Ties rank after the entries already in the table
+    assert_eq!(leaderboard.submit(entry("Dan", 200)), Ok(Some(2)));
+
+    let difficulty = entry("Ann", 0).replay.difficulty().unwrap();
+    let names: Vec<_> = leaderboard
+        .entries(&difficulty)
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect();
+    assert_eq!(names, ["Bob", "Cid", "Dan", "Ann"]);
+}
+
+#[test]
+fn leaderboard_is_capped() {
+    let mut leaderboard = Leaderboard::default();
+    for i in 0..LEADERBOARD_SIZE as u32 {
+        leaderboard.submit(entry("Ann", 100 + i)).unwrap();
+    }
+
+    assert_eq!(leaderboard.submit(entry("Bob", 1000)), Ok(None));
+    assert_eq!(
+        leaderboard.submit(entry("Cid", 50)),
+        Ok(Some(0)),
+        "faster entries still make the table"
+    );
+
+    let difficulty = entry("Ann", 0).replay.difficulty().unwrap();
+    assert_eq!(leaderboard.entries(&difficulty).len(), LEADERBOARD_SIZE);
+}
+
+#[test]
+fn board_variants_are_ranked_apart() {
+    let difficulty =
+        |options: BoardOptions| Replay::new(options, SEED).difficulty();
+    let classic = difficulty(empty_board());
+
+    for options in [
+        BoardOptions {
+            shape: BoardShape::Circle,
+            ..empty_board()
+        },
+        BoardOptions {
+            topology: Topology::Toroidal,
+            ..empty_board()
+        },
+        BoardOptions {
+            neighborhood: Neighborhood::Knight,
+            ..empty_board()
+        },
+        BoardOptions {
+            max_bombs_per_tile: 2,
+            ..empty_board()
+        },
+    ] {
+        let variant = difficulty(options);
+        assert!(variant.is_some());
+        assert_ne!(variant, classic);
+    }
+}
+
+#[test]
+fn custom_boards_are_not_ranked() {
+    let mask = TileMask::parse("##\n##").unwrap();
+    let mut masked = entry("Ann", 100);
+    masked.replay.options.shape = BoardShape::Mask(mask);
+
+    assert_eq!(masked.replay.difficulty(), None);
+    assert_eq!(
+        Leaderboard::default().submit(masked),
+        Err(LeaderboardError::Unranked)
+    );
+}
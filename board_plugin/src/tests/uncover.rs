@@ -0,0 +1,102 @@
+use super::harness::{BoardHarness, BOARD, PLAYER};
+use crate::components::Coordinates;
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, CascadeEvent, TileUncoveredEvent,
+};
+use crate::resources::tile::Tile;
+
+/// Bombs on the middle column of a 5x5 board
+const WALL: [(u16, u16); 5] = [(2, 0), (2, 1), (2, 2), (2, 3), (2, 4)];
+
+#[test]
+fn empty_tile_cascades_to_its_region() {
+    let mut harness = BoardHarness::new(5, 5, &WALL);
+
+    harness.reveal(0, 0);
+
+    for y in 0..5 {
+        assert!(!harness.is_covered(0, y));
+        assert!(!harness.is_covered(1, y));
+        assert!(harness.is_covered(2, y));
+        assert!(harness.is_covered(3, y));
+        assert!(harness.is_covered(4, y));
+    }
+    assert_eq!(harness.events::<TileUncoveredEvent>().len(), 10);
+
+    let cascades = harness.events::<CascadeEvent>();
+    assert_eq!(cascades.len(), 1);
+    assert_eq!(cascades[0].board, BOARD);
+    assert_eq!(cascades[0].player, PLAYER);
+    assert_eq!(cascades[0].origin, Coordinates::new(0, 0));
+    assert_eq!(cascades[0].tiles, 9);
+}
+
+#[test]
+fn bomb_neighbor_does_not_cascade() {
+    let mut harness = BoardHarness::new(5, 5, &WALL);
+
+    harness.reveal(1, 2);
+
+    assert!(!harness.is_covered(1, 2));
+    assert!(harness.is_covered(0, 2));
+    assert_eq!(harness.board().covered_tiles.len(), 24);
+
+    let uncovered = harness.events::<TileUncoveredEvent>();
+    assert_eq!(uncovered.len(), 1);
+    assert_eq!(uncovered[0].tile, Tile::BombNeighbor(3));
+    assert!(!uncovered[0].cascade);
+    assert!(harness.events::<CascadeEvent>().is_empty());
+}
+
+#[test]
+fn revealing_a_bomb_explodes() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+
+    harness.reveal(1, 1);
+
+    let explosions = harness.events::<BombExplosionEvent>();
+    assert_eq!(explosions.len(), 1);
+    assert_eq!(explosions[0].board, BOARD);
+    assert_eq!(explosions[0].player, PLAYER);
+    assert_eq!(explosions[0].coordinates, Some(Coordinates::new(1, 1)));
+    assert!(harness.events::<BoardCompletedEvent>().is_empty());
+}
+
+#[test]
+fn revealing_every_safe_tile_completes_the_board() {
+    let mut harness = BoardHarness::new(3, 3, &[(1, 1)]);
+    let safe_tiles = [
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (0, 1),
+        (2, 1),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+    ];
+
+    for (x, y) in safe_tiles {
+        assert!(harness.events::<BoardCompletedEvent>().is_empty());
+        harness.reveal(x, y);
+    }
+
+    let completed = harness.events::<BoardCompletedEvent>();
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].board, BOARD);
+    assert_eq!(completed[0].player, PLAYER);
+    assert_eq!(completed[0].bombs, 1);
+    assert!(harness.board().is_completed());
+    assert!(harness.events::<BombExplosionEvent>().is_empty());
+}
+
+#[test]
+fn cascade_can_complete_the_board() {
+    let mut harness = BoardHarness::new(4, 4, &[(3, 3)]);
+
+    harness.reveal(0, 0);
+
+    assert_eq!(harness.board().covered_tiles.len(), 1);
+    assert!(harness.is_covered(3, 3));
+    assert_eq!(harness.events::<BoardCompletedEvent>().len(), 1);
+}
@@ -0,0 +1,43 @@
+//! Headless game server for networked games
+//!
+//! Usage: `server [address] [players] [race|coop] [seed]`
+
+use bevy::log::{self, LogPlugin};
+use bevy::prelude::*;
+use board_plugin::net::protocol::GameMode;
+use board_plugin::net::server::{self, ServerOptions};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    // Only used to set up logging
+    App::new().add_plugins(LogPlugin::default());
+
+    let mut args = std::env::args().skip(1);
+    let mut options = ServerOptions {
+        seed: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default(),
+        ..default()
+    };
+
+    if let Some(address) = args.next() {
+        options.address = address;
+    }
+    if let Some(players) = args.next().and_then(|p| p.parse().ok()) {
+        options.players = players;
+    }
+    if let Some(mode) = args.next() {
+        options.mode = match mode.as_str() {
+            "coop" => GameMode::Coop,
+            _ => GameMode::Race,
+        };
+    }
+    if let Some(seed) = args.next().and_then(|s| s.parse().ok()) {
+        options.seed = seed;
+    }
+
+    if let Err(e) = server::run(options) {
+        log::error!("Server error: {e}");
+    }
+}
@@ -5,16 +5,44 @@ use bevy::{
     prelude::*,
 };
 use board_plugin::{
-    resources::{BoardAssets, BoardPosition, SpriteMaterial},
-    BoardOptions, BoardPlugin,
+    components::{BoardInput, KeyboardCursor},
+    net::{NetClientOptions, NetClientPlugin},
+    resources::{
+        AccessibilityPreferences, BoardAssets, BoardPlayers, BoardPosition,
+        BoardShape, BoardSkin, BoardSounds, DailyChallenge, EndlessOptions,
+        GameMode, InputAction, PlayerOptions, Preferences, SpriteMaterial,
+        TileMask, PLAYER_COLORS, UI_SCALE_STEP, VOLUME_STEP,
+    },
+    ActionInput, AnimationPlugin, AnnouncerPlugin, BindingsPlugin,
+    BoardAudioPlugin, BoardOptions, BoardPlugin, DailyChallengePlugin,
+    EndScreenPlugin, EndlessBoardPlugin, GameLogPlugin, LeaderboardPlugin,
+    PausePlugin, PreferencesPlugin,
 };
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
+/// Board themes cycled with T, applied to the running board along with the
+/// accessibility preferences
+#[derive(Debug, Clone, Resource)]
+struct Themes {
+    themes: Vec<BoardAssets>,
+    current: usize,
+}
+
+/// Game being restarted, it is loaded again once cleared
+#[derive(Debug, Resource)]
+struct Restarting;
+
+/// Mask of the diamond shaped board, loaded with B
+#[derive(Debug, Resource)]
+struct DiamondMask(Handle<TileMask>);
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default, States)]
 pub enum AppState {
     InGame,
+    Endless,
+    Online,
     #[default]
     Out,
 }
@@ -41,9 +69,49 @@ fn main() {
             BoardPlugin {
                 running_state: AppState::InGame,
             },
+            EndlessBoardPlugin {
+                running_state: AppState::Endless,
+            },
+            NetClientPlugin {
+                running_state: AppState::Online,
+            },
+            DailyChallengePlugin {
+                running_state: AppState::InGame,
+            },
+            LeaderboardPlugin {
+                running_state: AppState::InGame,
+            },
+            EndScreenPlugin {
+                running_state: AppState::InGame,
+            },
+            AnimationPlugin {
+                running_state: AppState::InGame,
+            },
+            BoardAudioPlugin {
+                running_state: AppState::InGame,
+            },
+            AnnouncerPlugin {
+                running_state: AppState::InGame,
+            },
+            PausePlugin {
+                running_state: AppState::InGame,
+            },
+            GameLogPlugin {
+                running_state: AppState::InGame,
+            },
+            PreferencesPlugin,
+            BindingsPlugin,
         ))
         .add_systems(Startup, (setup_camera, setup_board))
-        .add_systems(Update, handle_input);
+        .add_systems(Update, (handle_input, handle_theme_input))
+        .add_systems(
+            Update,
+            (handle_audio_input, handle_accessibility_input, apply_theme)
+                .run_if(resource_exists::<Preferences>()),
+        )
+        .add_systems(Update, pan_camera.run_if(in_state(AppState::Endless)))
+        .add_systems(OnEnter(AppState::Out), finish_restart)
+        .add_systems(OnExit(AppState::Endless), reset_camera);
 
     #[cfg(feature = "debug")]
     app.add_plugins(WorldInspectorPlugin::new());
@@ -55,18 +123,21 @@ fn setup_board(
     mut commands: Commands,
     mut next_state: ResMut<NextState<AppState>>,
     asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
 ) {
-    commands.insert_resource(BoardOptions {
-        map_size: (20, 20),
-        bomb_count: 50,
-        tile_padding: 1.0,
-        safe_start: true,
-        position: BoardPosition::Centered {
-            offset: Vec3::new(0., 25., 0.),
-        },
+    commands.insert_resource(standard_options());
+    commands
+        .insert_resource(DiamondMask(asset_server.load("masks/diamond.mask")));
+    commands.insert_resource(NetClientOptions {
+        address: std::env::var("MINESWEEPER_SERVER")
+            .unwrap_or_else(|_| NetClientOptions::default().address),
         ..default()
     });
-    commands.insert_resource(BoardAssets {
+    commands.insert_resource(EndlessOptions {
+        seed: rand_seed(),
+        ..default()
+    });
+    let default_theme = BoardAssets {
         label: "Default".to_string(),
         board_material: SpriteMaterial {
             color: Color::WHITE,
@@ -86,41 +157,393 @@ fn setup_board(
             color: Color::WHITE,
             texture: asset_server.load("sprites/flag.png"),
         },
+        question_material: SpriteMaterial {
+            color: Color::WHITE,
+            texture: asset_server.load("sprites/question.png"),
+        },
         bomb_material: SpriteMaterial {
             color: Color::WHITE,
             texture: asset_server.load("sprites/bomb.png"),
         },
+        skin: None,
+    };
+    let light_theme = BoardAssets {
+        label: "Light".to_string(),
+        board_material: SpriteMaterial {
+            color: Color::rgb(0.75, 0.75, 0.75),
+            ..default()
+        },
+        tile_material: SpriteMaterial {
+            color: Color::rgb(0.95, 0.95, 0.95),
+            ..default()
+        },
+        covered_tile_material: SpriteMaterial {
+            color: Color::rgb(0.6, 0.7, 0.8),
+            ..default()
+        },
+        bomb_counter_colors: vec![
+            Color::BLUE,
+            Color::DARK_GREEN,
+            Color::RED,
+            Color::NAVY,
+            Color::MAROON,
+            Color::TEAL,
+            Color::BLACK,
+            Color::GRAY,
+        ],
+        flag_material: SpriteMaterial {
+            color: Color::RED,
+            ..default_theme.flag_material.clone()
+        },
+        bomb_material: SpriteMaterial {
+            color: Color::BLACK,
+            ..default_theme.bomb_material.clone()
+        },
+        ..default_theme.clone()
+    };
+    let dark_theme = BoardAssets {
+        label: "Dark".to_string(),
+        board_material: SpriteMaterial {
+            color: Color::rgb(0.05, 0.05, 0.08),
+            ..default()
+        },
+        tile_material: SpriteMaterial {
+            color: Color::rgb(0.15, 0.15, 0.2),
+            ..default()
+        },
+        covered_tile_material: SpriteMaterial {
+            color: Color::rgb(0.3, 0.3, 0.4),
+            ..default()
+        },
+        ..default_theme.clone()
+    };
+    let classic_theme = BoardAssets {
+        label: "Classic".to_string(),
+        board_material: SpriteMaterial {
+            color: Color::rgb(0.5, 0.5, 0.5),
+            ..default()
+        },
+        skin: Some(BoardSkin {
+            label: "Classic".to_string(),
+            atlas: texture_atlases.add(BoardSkin::atlas(
+                asset_server.load("skins/classic.png"),
+                16.,
+            )),
+        }),
+        ..default_theme.clone()
+    };
+    commands.insert_resource(default_theme.clone());
+    commands.insert_resource(Themes {
+        themes: vec![default_theme, light_theme, dark_theme, classic_theme],
+        current: 0,
+    });
+    commands.insert_resource(BoardSounds {
+        reveal: asset_server.load("sounds/reveal.wav"),
+        cascade: asset_server.load("sounds/cascade.wav"),
+        flag: asset_server.load("sounds/flag.wav"),
+        unflag: asset_server.load("sounds/unflag.wav"),
+        chord: asset_server.load("sounds/chord.wav"),
+        explosion: asset_server.load("sounds/explosion.wav"),
+        win: asset_server.load("sounds/win.wav"),
     });
 
     next_state.set(AppState::InGame);
 }
 
+/// Options of the regular boards
+fn standard_options() -> BoardOptions {
+    BoardOptions {
+        map_size: (20, 20),
+        bomb_count: 50,
+        tile_padding: 1.0,
+        safe_start: true,
+        question_marks: true,
+        position: BoardPosition::Centered {
+            offset: Vec3::new(0., 25., 0.),
+        },
+        ..default()
+    }
+}
+
 fn setup_camera(mut commands: Commands) {
     // 2D orthographic camera
     commands.spawn(Camera2dBundle::default());
 }
 
+/// Seed for the endless board, changes on every launch
+fn rand_seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Move the camera around the endless board with the arrow keys
+fn pan_camera(
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    const SPEED: f32 = 500.;
+
+    let direction = [
+        (KeyCode::Left, Vec3::NEG_X),
+        (KeyCode::Right, Vec3::X),
+        (KeyCode::Down, Vec3::NEG_Y),
+        (KeyCode::Up, Vec3::Y),
+    ]
+    .into_iter()
+    .filter(|(key, _)| keys.pressed(*key))
+    .map(|(_, direction)| direction)
+    .sum::<Vec3>();
+
+    for mut transform in camera_query.iter_mut() {
+        transform.translation += direction * SPEED * time.delta_seconds();
+    }
+}
+
+fn reset_camera(mut camera_query: Query<&mut Transform, With<Camera2d>>) {
+    for mut transform in camera_query.iter_mut() {
+        transform.translation.x = 0.;
+        transform.translation.y = 0.;
+    }
+}
+
 fn handle_input(
+    mut commands: Commands,
     state: Res<State<AppState>>,
     mut next_state: ResMut<NextState<AppState>>,
     keys: Res<Input<KeyCode>>,
+    actions: ActionInput,
+    diamond_mask: Res<DiamondMask>,
 ) {
-    if keys.just_pressed(KeyCode::C) {
+    if actions.just_pressed(InputAction::Clear) {
         log::debug!("clearing detected");
 
-        if state.get() == &AppState::InGame {
+        if state.get() != &AppState::Out {
             log::info!("clearing game");
 
             next_state.set(AppState::Out);
         }
     }
-    if keys.just_pressed(KeyCode::G) {
-        log::debug!("loading detected");
+    if actions.just_pressed(InputAction::Restart) {
+        log::debug!("restart detected");
+
+        match state.get() {
+            AppState::Out => {
+                log::info!("loading game");
+
+                commands.remove_resource::<BoardPlayers>();
+                commands.remove_resource::<DailyChallenge>();
+                commands.insert_resource(standard_options());
+                next_state.set(AppState::InGame);
+            }
+            AppState::InGame => {
+                log::info!("restarting game");
+
+                commands.insert_resource(Restarting);
+                next_state.set(AppState::Out);
+            }
+            _ => (),
+        }
+    }
+    if keys.just_pressed(KeyCode::F) {
+        log::debug!("no flag loading detected");
+
+        if state.get() == &AppState::Out {
+            log::info!("loading no flag game");
+
+            commands.remove_resource::<BoardPlayers>();
+            commands.remove_resource::<DailyChallenge>();
+            commands.insert_resource(BoardOptions {
+                no_flag: true,
+                ..standard_options()
+            });
+            next_state.set(AppState::InGame);
+        }
+    }
+    if keys.just_pressed(KeyCode::B) {
+        log::debug!("diamond loading detected");
 
         if state.get() == &AppState::Out {
-            log::info!("loading game");
+            log::info!("loading diamond game");
 
+            commands.remove_resource::<BoardPlayers>();
+            commands.remove_resource::<DailyChallenge>();
+            commands.insert_resource(BoardOptions {
+                bomb_count: 25,
+                shape: BoardShape::Asset(diamond_mask.0.clone()),
+                ..standard_options()
+            });
             next_state.set(AppState::InGame);
         }
     }
+    if keys.just_pressed(KeyCode::R) {
+        log::debug!("race loading detected");
+
+        if state.get() == &AppState::Out {
+            log::info!("loading race game");
+
+            // Mouse player against a WASD keyboard player
+            commands.insert_resource(BoardPlayers {
+                players: two_players(),
+                mode: GameMode::Race,
+            });
+            commands.remove_resource::<DailyChallenge>();
+            commands.insert_resource(standard_options());
+            next_state.set(AppState::InGame);
+        }
+    }
+    if keys.just_pressed(KeyCode::O) {
+        log::debug!("coop loading detected");
+
+        if state.get() == &AppState::Out {
+            log::info!("loading coop game");
+
+            // Mouse player and WASD keyboard player on a shared board
+            commands.insert_resource(BoardPlayers {
+                players: two_players(),
+                mode: GameMode::Coop,
+            });
+            commands.remove_resource::<DailyChallenge>();
+            commands.insert_resource(standard_options());
+            next_state.set(AppState::InGame);
+        }
+    }
+    if keys.just_pressed(KeyCode::D) {
+        log::debug!("daily loading detected");
+
+        if state.get() == &AppState::Out {
+            log::info!("loading daily challenge");
+
+            let challenge = DailyChallenge::today();
+            commands
+                .insert_resource(challenge.board_options(&standard_options()));
+            commands.insert_resource(challenge);
+            commands.remove_resource::<BoardPlayers>();
+            next_state.set(AppState::InGame);
+        }
+    }
+    if keys.just_pressed(KeyCode::E) {
+        log::debug!("endless loading detected");
+
+        if state.get() == &AppState::Out {
+            log::info!("loading endless game");
+
+            next_state.set(AppState::Endless);
+        }
+    }
+    if keys.just_pressed(KeyCode::N) {
+        log::debug!("online loading detected");
+
+        if state.get() == &AppState::Out {
+            log::info!("joining online game");
+
+            next_state.set(AppState::Online);
+        }
+    }
+}
+
+/// Start the game over with the same options once the previous one is
+/// cleared
+fn finish_restart(
+    mut commands: Commands,
+    restarting: Option<Res<Restarting>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if restarting.is_some() {
+        commands.remove_resource::<Restarting>();
+        next_state.set(AppState::InGame);
+    }
+}
+
+/// Switch to the next theme with T
+fn handle_theme_input(keys: Res<Input<KeyCode>>, mut themes: ResMut<Themes>) {
+    if keys.just_pressed(KeyCode::T) {
+        themes.current = (themes.current + 1) % themes.themes.len();
+        log::info!("theme {}", themes.themes[themes.current].label);
+    }
+}
+
+/// Cycle the counter palette with F2, toggle counter glyphs with F3 and high
+/// contrast with F4, change the UI scale with F5 and F6
+fn handle_accessibility_input(
+    keys: Res<Input<KeyCode>>,
+    mut preferences: ResMut<Preferences>,
+) {
+    if keys.just_pressed(KeyCode::F2) {
+        let accessibility = &mut preferences.accessibility;
+        accessibility.palette = accessibility.palette.next();
+        log::info!("palette {}", accessibility.palette);
+    }
+    if keys.just_pressed(KeyCode::F3) {
+        let accessibility = &mut preferences.accessibility;
+        accessibility.glyphs = !accessibility.glyphs;
+        log::info!("counter glyphs {}", accessibility.glyphs);
+    }
+    if keys.just_pressed(KeyCode::F4) {
+        let accessibility = &mut preferences.accessibility;
+        accessibility.high_contrast = !accessibility.high_contrast;
+        log::info!("high contrast {}", accessibility.high_contrast);
+    }
+    if keys.just_pressed(KeyCode::F5) {
+        preferences.accessibility.adjust_ui_scale(-UI_SCALE_STEP);
+        log::info!("ui scale {}", preferences.accessibility.ui_scale);
+    }
+    if keys.just_pressed(KeyCode::F6) {
+        preferences.accessibility.adjust_ui_scale(UI_SCALE_STEP);
+        log::info!("ui scale {}", preferences.accessibility.ui_scale);
+    }
+}
+
+/// Apply the current theme and the accessibility preferences, when either
+/// changed
+fn apply_theme(
+    themes: Res<Themes>,
+    preferences: Res<Preferences>,
+    mut board_assets: ResMut<BoardAssets>,
+    mut ui_scale: ResMut<UiScale>,
+    mut applied: Local<Option<(usize, AccessibilityPreferences)>>,
+) {
+    let current = (themes.current, preferences.accessibility.clone());
+    if applied.as_ref() == Some(&current) {
+        return;
+    }
+
+    *board_assets = themes.themes[current.0].accessible(&current.1);
+    ui_scale.scale = f64::from(current.1.ui_scale);
+    *applied = Some(current);
+}
+
+/// Mute with M, change the volume with - and =
+fn handle_audio_input(
+    keys: Res<Input<KeyCode>>,
+    mut preferences: ResMut<Preferences>,
+) {
+    if keys.just_pressed(KeyCode::M) {
+        let audio = &mut preferences.audio;
+        audio.muted = !audio.muted;
+        log::info!("sound {}", if audio.muted { "muted" } else { "unmuted" });
+    }
+    if keys.just_pressed(KeyCode::Minus) {
+        preferences.audio.adjust_volume(-VOLUME_STEP);
+        log::info!("volume {:.0}%", preferences.audio.volume * 100.);
+    }
+    if keys.just_pressed(KeyCode::Equals) {
+        preferences.audio.adjust_volume(VOLUME_STEP);
+        log::info!("volume {:.0}%", preferences.audio.volume * 100.);
+    }
+}
+
+/// Mouse player and WASD keyboard player
+fn two_players() -> Vec<PlayerOptions> {
+    vec![
+        PlayerOptions::default(),
+        PlayerOptions {
+            input: BoardInput::Keyboard(KeyboardCursor::default()),
+            color: PLAYER_COLORS[1],
+        },
+    ]
 }